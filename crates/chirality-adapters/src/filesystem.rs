@@ -0,0 +1,589 @@
+//! Filesystem adapter - `WorkspacePort` over a local workspace directory.
+
+use async_trait::async_trait;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use chirality_domain::{resolve_path, ContentHash, Deliverable, DocumentType, StatusFile};
+use chirality_ports::{FsChangeStream, PortError, StagedChange, WorkspacePort, WorkspaceTransaction};
+
+use crate::fs_transaction::{self, Change};
+use crate::fs_watch;
+
+/// Quiet period before a batch of watch events is delivered.
+pub const DEFAULT_WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Longest a batch of watch events is held back when the subtree never goes quiet.
+pub const DEFAULT_WATCH_MAX_WAIT: Duration = Duration::from_secs(2);
+
+/// A `WorkspacePort` rooted at a workspace directory.
+///
+/// Relative paths resolve against the root; absolute paths are accepted as
+/// long as they stay inside it. Paths are resolved through symlinks, and
+/// anything that escapes the root (via `..`, or a link pointing outside) is
+/// refused with `PortError::PermissionDenied`.
+///
+/// Conditional writes are serialised per adapter (and its clones), so two
+/// `write_if_unchanged` callers cannot both pass the hash check. Nothing else
+/// takes that lock: a plain `write`, another adapter over the same root, or
+/// an external editor saving between check and rename can still slip through.
+///
+/// Transactions are journaled under `.chirality/transactions/` in the root.
+/// Open the workspace with [`FilesystemAdapter::open`] at process startup so
+/// commits interrupted by a crash are rolled back before anything reads it.
+#[derive(Debug, Clone)]
+pub struct FilesystemAdapter {
+    root: PathBuf,
+    watch_debounce: Duration,
+    watch_max_wait: Duration,
+    conditional_writes: Arc<Mutex<()>>,
+}
+
+impl FilesystemAdapter {
+    /// Open an existing workspace directory.
+    ///
+    /// The root is canonicalised, so a relative root such as `.` is pinned to
+    /// the directory it names now. An empty or missing root is an error.
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, PortError> {
+        let root = root.into();
+        if root.as_os_str().is_empty() {
+            return Err(PortError::Io {
+                message: "workspace root must not be empty".to_string(),
+            });
+        }
+        let root = root.canonicalize().map_err(|e| port_error(e, &root))?;
+        if !root.is_dir() {
+            return Err(PortError::Io {
+                message: format!("workspace root {} is not a directory", root.display()),
+            });
+        }
+        Ok(Self {
+            root,
+            watch_debounce: DEFAULT_WATCH_DEBOUNCE,
+            watch_max_wait: DEFAULT_WATCH_MAX_WAIT,
+            conditional_writes: Arc::new(Mutex::new(())),
+        })
+    }
+
+    /// Open the workspace and roll back any interrupted transactions.
+    ///
+    /// Use this once per workspace when the process starts. Adapters created
+    /// later (or clones) should use [`FilesystemAdapter::new`]: recovering
+    /// while another adapter is mid-commit would undo its transaction.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, PortError> {
+        let adapter = Self::new(root)?;
+        for id in fs_transaction::recover(&adapter.root)? {
+            tracing::warn!(transaction = %id, root = %adapter.root.display(), "rolled back interrupted transaction");
+        }
+        Ok(adapter)
+    }
+
+    pub fn with_watch_debounce(mut self, debounce: Duration) -> Self {
+        self.watch_debounce = debounce;
+        self
+    }
+
+    pub fn with_watch_max_wait(mut self, max_wait: Duration) -> Self {
+        self.watch_max_wait = max_wait;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve a path against the workspace root, following symlinks.
+    pub fn resolve(&self, path: &Path) -> Result<PathBuf, PortError> {
+        match resolve_path(&self.root.join(path)) {
+            Some(resolved) if resolved.starts_with(&self.root) => Ok(resolved),
+            _ => Err(PortError::PermissionDenied {
+                path: path.to_path_buf(),
+                reason: format!("outside the workspace root {}", self.root.display()),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl WorkspacePort for FilesystemAdapter {
+    async fn read(&self, path: &Path) -> Result<Vec<u8>, PortError> {
+        let path = self.resolve(path)?;
+        tokio::fs::read(&path).await.map_err(|e| port_error(e, &path))
+    }
+
+    async fn write(&self, path: &Path, content: &[u8]) -> Result<ContentHash, PortError> {
+        let path = self.resolve(path)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| port_error(e, parent))?;
+        }
+        tokio::fs::write(&path, content)
+            .await
+            .map_err(|e| port_error(e, &path))?;
+        Ok(ContentHash::from_bytes(content))
+    }
+
+    async fn write_if_unchanged(
+        &self,
+        path: &Path,
+        content: &[u8],
+        expected: Option<&ContentHash>,
+    ) -> Result<ContentHash, PortError> {
+        let resolved = self.resolve(path)?;
+        let _guard = self.conditional_writes.lock().await;
+        let actual = match tokio::fs::read(&resolved).await {
+            Ok(bytes) => Some(ContentHash::from_bytes(&bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(port_error(e, &resolved)),
+        };
+        if actual.as_ref() != expected {
+            return Err(PortError::WriteConflict {
+                path: path.to_path_buf(),
+                expected: expected.cloned(),
+                actual,
+            });
+        }
+        // Write a uniquely named file beside the target and rename it into
+        // place, so readers never see a partial file. The temp file removes
+        // itself if anything fails before the rename.
+        let parent = resolved.parent().unwrap_or(&self.root).to_path_buf();
+        tokio::fs::create_dir_all(&parent)
+            .await
+            .map_err(|e| port_error(e, &parent))?;
+        let prefix = format!(".{}.", resolved.file_name().unwrap_or_default().to_string_lossy());
+        let bytes = content.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let mut temp = tempfile::Builder::new()
+                .prefix(&prefix)
+                .suffix(".tmp")
+                .tempfile_in(&parent)
+                .map_err(|e| port_error(e, &parent))?;
+            temp.write_all(&bytes)
+                .and_then(|_| temp.as_file().sync_all())
+                .map_err(|e| port_error(e, temp.path()))?;
+            temp.persist(&resolved).map_err(|e| port_error(e.error, &resolved))?;
+            Ok::<_, PortError>(())
+        })
+        .await
+        .map_err(|e| PortError::Internal {
+            message: e.to_string(),
+        })??;
+        Ok(ContentHash::from_bytes(content))
+    }
+
+    async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>, PortError> {
+        let path = self.resolve(path)?;
+        let mut entries = tokio::fs::read_dir(&path)
+            .await
+            .map_err(|e| port_error(e, &path))?;
+        let mut paths = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| port_error(e, &path))?
+        {
+            paths.push(entry.path());
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, PortError> {
+        let path = self.resolve(path)?;
+        tokio::fs::try_exists(&path)
+            .await
+            .map_err(|e| port_error(e, &path))
+    }
+
+    async fn is_dir(&self, path: &Path) -> Result<bool, PortError> {
+        let path = self.resolve(path)?;
+        match tokio::fs::metadata(&path).await {
+            Ok(metadata) => Ok(metadata.is_dir()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(port_error(e, &path)),
+        }
+    }
+
+    async fn hash(&self, path: &Path) -> Result<ContentHash, PortError> {
+        let content = self.read(path).await?;
+        Ok(ContentHash::from_bytes(&content))
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> Result<(), PortError> {
+        let path = self.resolve(path)?;
+        tokio::fs::create_dir_all(&path)
+            .await
+            .map_err(|e| port_error(e, &path))
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), PortError> {
+        let path = self.resolve(path)?;
+        tokio::fs::remove_file(&path)
+            .await
+            .map_err(|e| port_error(e, &path))
+    }
+
+    /// Create the deliverable folder with the four core documents and five
+    /// metadata files. Existing files are left untouched, so scaffolding is
+    /// safe to repeat.
+    async fn scaffold_deliverable(&self, deliverable: &Deliverable) -> Result<(), PortError> {
+        self.create_dir_all(&deliverable.folder_path).await?;
+        for doc_type in DocumentType::ALL {
+            let path = deliverable.folder_path.join(doc_type.filename());
+            if self.exists(&path).await? {
+                continue;
+            }
+            let content = match doc_type {
+                DocumentType::Status => StatusFile::new(deliverable.state).render(),
+                _ => String::new(),
+            };
+            self.write(&path, content.as_bytes()).await?;
+        }
+        Ok(())
+    }
+
+    /// Must be called from within a Tokio runtime.
+    async fn watch(&self, path: &Path) -> Result<FsChangeStream, PortError> {
+        let path = self.resolve(path)?;
+        fs_watch::watch(&path, self.watch_debounce, self.watch_max_wait)
+    }
+
+    async fn commit_transaction(
+        &self,
+        transaction: WorkspaceTransaction,
+    ) -> Result<Vec<(PathBuf, ContentHash)>, PortError> {
+        // The transaction dedups by the path as written; two spellings of the
+        // same file would share temp and backup names, so dedup again here.
+        let mut staged: Vec<(&Path, Change)> = Vec::new();
+        for change in transaction.changes() {
+            let target = self.resolve(change.path())?;
+            let content = match change {
+                StagedChange::Write { content, .. } => Some(content.clone()),
+                StagedChange::Delete { .. } => None,
+            };
+            staged.retain(|(_, c)| c.target != target);
+            staged.push((change.path(), Change { target, content }));
+        }
+        let written: Vec<PathBuf> = staged
+            .iter()
+            .filter(|(_, c)| c.content.is_some())
+            .map(|(path, _)| path.to_path_buf())
+            .collect();
+        let changes: Vec<Change> = staged.into_iter().map(|(_, c)| c).collect();
+        let root = self.root.clone();
+        let id = transaction.id().to_string();
+        let hashes = tokio::task::spawn_blocking(move || fs_transaction::commit(&root, &id, &changes))
+            .await
+            .map_err(|e| PortError::Internal {
+                message: e.to_string(),
+            })??;
+        Ok(written.into_iter().zip(hashes).collect())
+    }
+
+    async fn recover_transactions(&self) -> Result<Vec<String>, PortError> {
+        let root = self.root.clone();
+        tokio::task::spawn_blocking(move || fs_transaction::recover(&root))
+            .await
+            .map_err(|e| PortError::Internal {
+                message: e.to_string(),
+            })?
+    }
+}
+
+/// Map an I/O error on `path` to the matching `PortError`.
+pub(crate) fn port_error(err: io::Error, path: &Path) -> PortError {
+    match err.kind() {
+        io::ErrorKind::NotFound => PortError::FileNotFound {
+            path: path.to_path_buf(),
+        },
+        io::ErrorKind::PermissionDenied => PortError::PermissionDenied {
+            path: path.to_path_buf(),
+            reason: err.to_string(),
+        },
+        _ => PortError::Io {
+            message: format!("{}: {}", path.display(), err),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chirality_domain::{DeliverableState, PackageId};
+    use chirality_ports::{FsChangeEvent, FsChangeType};
+    use futures_util::StreamExt;
+
+    fn adapter() -> (tempfile::TempDir, FilesystemAdapter) {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter = FilesystemAdapter::new(dir.path()).unwrap();
+        (dir, adapter)
+    }
+
+    #[test]
+    fn root_must_exist_and_is_canonicalised() {
+        assert!(matches!(FilesystemAdapter::new(""), Err(PortError::Io { .. })));
+        assert!(matches!(
+            FilesystemAdapter::new("/definitely/not/a/workspace"),
+            Err(PortError::FileNotFound { .. })
+        ));
+
+        let fs = FilesystemAdapter::new(".").unwrap();
+        assert!(fs.root().is_absolute());
+        assert_eq!(fs.root(), std::env::current_dir().unwrap().canonicalize().unwrap());
+        assert!(matches!(
+            fs.resolve(Path::new("/etc/hostname")),
+            Err(PortError::PermissionDenied { .. })
+        ));
+        assert!(matches!(
+            fs.resolve(Path::new("../outside.md")),
+            Err(PortError::PermissionDenied { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlinks_pointing_outside_are_refused() {
+        let (dir, fs) = adapter();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.md"), "secret").unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("escape")).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret.md"), dir.path().join("secret.md")).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("PKG-001"), dir.path().join("inside")).unwrap();
+        std::fs::create_dir_all(dir.path().join("PKG-001")).unwrap();
+
+        for path in ["escape/secret.md", "escape/new.md", "secret.md"] {
+            assert!(
+                matches!(fs.read(Path::new(path)).await, Err(PortError::PermissionDenied { .. })),
+                "{}",
+                path
+            );
+        }
+        assert!(matches!(
+            fs.write(Path::new("escape/new.md"), b"x").await,
+            Err(PortError::PermissionDenied { .. })
+        ));
+        // A `..` after a missing component must still see the link.
+        for path in ["nope/../escape/b.md", "a/b/../../secret.md"] {
+            assert!(
+                matches!(fs.write(Path::new(path), b"x").await, Err(PortError::PermissionDenied { .. })),
+                "{}",
+                path
+            );
+        }
+        assert!(!outside.path().join("new.md").exists());
+        assert!(!outside.path().join("b.md").exists());
+        assert_eq!(std::fs::read_to_string(outside.path().join("secret.md")).unwrap(), "secret");
+
+        fs.write(Path::new("inside/notes.md"), b"ok").await.unwrap();
+        assert_eq!(fs.read(Path::new("PKG-001/notes.md")).await.unwrap(), b"ok");
+    }
+
+    #[tokio::test]
+    async fn write_read_and_hash_round_trip() {
+        let (dir, fs) = adapter();
+        let hash = fs.write(Path::new("PKG-001/notes.md"), b"# Notes\n").await.unwrap();
+
+        assert_eq!(hash, ContentHash::from_bytes(b"# Notes\n"));
+        assert_eq!(fs.hash(&dir.path().join("PKG-001/notes.md")).await.unwrap(), hash);
+        assert_eq!(fs.read(Path::new("PKG-001/./notes.md")).await.unwrap(), b"# Notes\n");
+        assert!(fs.is_dir(Path::new("PKG-001")).await.unwrap());
+        assert!(!fs.is_dir(Path::new("PKG-002")).await.unwrap());
+        assert_eq!(
+            fs.list_dir(Path::new("PKG-001")).await.unwrap(),
+            [dir.path().join("PKG-001/notes.md")]
+        );
+
+        fs.delete(Path::new("PKG-001/notes.md")).await.unwrap();
+        assert!(!fs.exists(Path::new("PKG-001/notes.md")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn maps_missing_files_and_escapes_to_port_errors() {
+        let (_dir, fs) = adapter();
+        assert!(matches!(
+            fs.read(Path::new("missing.md")).await,
+            Err(PortError::FileNotFound { .. })
+        ));
+        assert!(matches!(
+            fs.delete(Path::new("missing.md")).await,
+            Err(PortError::FileNotFound { .. })
+        ));
+        assert!(matches!(
+            fs.list_dir(Path::new("missing")).await,
+            Err(PortError::FileNotFound { .. })
+        ));
+        assert!(matches!(
+            fs.write(Path::new("../outside.md"), b"x").await,
+            Err(PortError::PermissionDenied { .. })
+        ));
+        assert!(matches!(
+            fs.read(Path::new("/etc/hostname")).await,
+            Err(PortError::PermissionDenied { .. })
+        ));
+        assert!(matches!(
+            port_error(io::ErrorKind::PermissionDenied.into(), Path::new("x")),
+            PortError::PermissionDenied { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn scaffolds_every_document_without_overwriting() {
+        let (dir, fs) = adapter();
+        let folder = dir.path().join("PKG-001_Civil/DEL-01.01_Foundation");
+        let deliverable = Deliverable::new(PackageId::from_legacy(1), "Foundation", folder.clone());
+        fs.write(&folder.join("Datasheet.md"), b"# Datasheet\n").await.unwrap();
+
+        fs.scaffold_deliverable(&deliverable).await.unwrap();
+        fs.scaffold_deliverable(&deliverable).await.unwrap();
+
+        let names: Vec<_> = fs
+            .list_dir(&folder)
+            .await
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names.len(), DocumentType::ALL.len());
+        for doc_type in DocumentType::ALL {
+            assert!(names.iter().any(|n| n == doc_type.filename()), "{}", doc_type.filename());
+        }
+        assert_eq!(fs.read(&folder.join("Datasheet.md")).await.unwrap(), b"# Datasheet\n");
+        let status = fs.read(&folder.join("_STATUS.md")).await.unwrap();
+        let status = StatusFile::parse(&String::from_utf8(status).unwrap()).unwrap();
+        assert_eq!(status.state, DeliverableState::Open);
+    }
+
+    #[tokio::test]
+    async fn watch_reports_debounced_changes_in_the_subtree() {
+        let (dir, fs) = adapter();
+        let fs = fs.with_watch_debounce(Duration::from_millis(300));
+        let folder = dir.path().join("PKG-001");
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("Datasheet.md"), "v1").unwrap();
+        let mut events = fs.watch(Path::new("PKG-001")).await.unwrap();
+
+        std::fs::write(folder.join("Datasheet.md"), "v2").unwrap();
+        std::fs::write(folder.join("Datasheet.md"), "v3").unwrap();
+        std::fs::write(folder.join(".Datasheet.md.swp"), "swap").unwrap();
+        std::fs::write(folder.join("notes.tmp"), "tmp").unwrap();
+        std::fs::rename(folder.join("notes.tmp"), folder.join("Notes.md")).unwrap();
+        std::fs::write(dir.path().join(".git/index"), "outside the subtree").unwrap();
+
+        // Collect until the stream has been idle well past the debounce
+        // window; a slow CI machine may split the writes across batches.
+        let mut received = Vec::new();
+        let mut idle = Duration::from_secs(10);
+        while let Ok(Some(event)) = tokio::time::timeout(idle, events.next()).await {
+            received.push(event);
+            idle = Duration::from_secs(2);
+        }
+        received.sort_by(|a, b| a.path.cmp(&b.path));
+        received.dedup();
+        assert_eq!(
+            received,
+            [
+                FsChangeEvent {
+                    path: folder.join("Datasheet.md"),
+                    change_type: FsChangeType::Modified,
+                },
+                FsChangeEvent {
+                    path: folder.join("Notes.md"),
+                    change_type: FsChangeType::Created,
+                },
+            ]
+        );
+        assert!(matches!(
+            fs.watch(Path::new("missing")).await,
+            Err(PortError::FileNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn commits_transactions_all_or_nothing() {
+        let (dir, fs) = adapter();
+        let folder = dir.path().join("PKG-001/DEL-01.01");
+        fs.write(&folder.join("Datasheet.md"), b"v1").await.unwrap();
+        fs.write(&folder.join("_REFERENCES.md"), b"refs").await.unwrap();
+
+        let mut transaction = WorkspaceTransaction::begin();
+        transaction
+            .write(folder.join("Datasheet.md"), "v2")
+            .write(folder.join("Guidance.md"), "new")
+            .delete(folder.join("_REFERENCES.md"));
+        let written = fs.commit_transaction(transaction).await.unwrap();
+        assert_eq!(
+            written,
+            [
+                (folder.join("Datasheet.md"), ContentHash::from_bytes(b"v2")),
+                (folder.join("Guidance.md"), ContentHash::from_bytes(b"new")),
+            ]
+        );
+        assert_eq!(
+            fs.list_dir(&folder).await.unwrap(),
+            [folder.join("Datasheet.md"), folder.join("Guidance.md")]
+        );
+
+        let mut failing = WorkspaceTransaction::begin();
+        failing
+            .write(folder.join("Datasheet.md"), "v3")
+            .delete(folder.join("missing.md"));
+        assert!(matches!(
+            fs.commit_transaction(failing).await,
+            Err(PortError::FileNotFound { .. })
+        ));
+        assert_eq!(fs.read(&folder.join("Datasheet.md")).await.unwrap(), b"v2");
+        assert_eq!(fs.list_dir(&folder).await.unwrap().len(), 2);
+        assert!(fs.recover_transactions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn transaction_paths_are_deduplicated_after_resolving() {
+        let (dir, fs) = adapter();
+        fs.write(Path::new("PKG-001/Datasheet.md"), b"v1").await.unwrap();
+
+        let mut transaction = WorkspaceTransaction::begin();
+        transaction
+            .write("PKG-001/Datasheet.md", "v2")
+            .write(dir.path().join("PKG-001/./Datasheet.md"), "v3");
+        assert_eq!(transaction.changes().len(), 2);
+        let written = fs.commit_transaction(transaction).await.unwrap();
+
+        assert_eq!(
+            written,
+            [(dir.path().join("PKG-001/./Datasheet.md"), ContentHash::from_bytes(b"v3"))]
+        );
+        assert_eq!(fs.read(Path::new("PKG-001/Datasheet.md")).await.unwrap(), b"v3");
+        assert_eq!(fs.list_dir(Path::new("PKG-001")).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn conditional_write_detects_concurrent_edits() {
+        let (dir, fs) = adapter();
+        let path = Path::new("Datasheet.md");
+        let v1 = fs.write_if_unchanged(path, b"v1", None).await.unwrap();
+        std::fs::write(dir.path().join("Datasheet.md"), "edited by hand").unwrap();
+
+        match fs.write_if_unchanged(path, b"v2", Some(&v1)).await {
+            Err(PortError::WriteConflict { expected, actual, .. }) => {
+                assert_eq!(expected, Some(v1));
+                assert_eq!(actual, Some(ContentHash::from_bytes(b"edited by hand")));
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+        assert_eq!(fs.read(path).await.unwrap(), b"edited by hand");
+        assert!(matches!(
+            fs.write_if_unchanged(path, b"v2", None).await,
+            Err(PortError::WriteConflict { expected: None, .. })
+        ));
+
+        let current = fs.hash(path).await.unwrap();
+        fs.write_if_unchanged(path, b"v2", Some(&current)).await.unwrap();
+        assert_eq!(fs.read(path).await.unwrap(), b"v2");
+        assert_eq!(fs.list_dir(Path::new("")).await.unwrap(), [dir.path().join("Datasheet.md")]);
+    }
+}
@@ -0,0 +1,296 @@
+//! Journaled multi-file commits for the filesystem adapter.
+//!
+//! A commit runs in four steps:
+//!
+//! 1. Write a journal (`.chirality/transactions/<id>.json`, state `PREPARED`)
+//!    naming every target, the temp file holding its new content and the
+//!    backup its current content will be moved to.
+//! 2. Write new content to the temp files, next to their targets.
+//! 3. Move each existing target to its backup, then rename the temp file into
+//!    place (deletes only do the first half).
+//! 4. Mark the journal `COMMITTED`, remove the backups and the journal.
+//!
+//! A `PREPARED` journal found by [`recover`] means the commit never finished:
+//! backups are moved back, files the transaction created are removed and temp
+//! files are deleted. A `COMMITTED` journal only needs its cleanup finished.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chirality_domain::ContentHash;
+use chirality_ports::PortError;
+
+use crate::filesystem::port_error;
+
+/// Journal directory, relative to the workspace root.
+pub(crate) const JOURNAL_DIR: &str = ".chirality/transactions";
+
+/// A resolved change: new content to write, or `None` to delete.
+pub(crate) struct Change {
+    pub target: PathBuf,
+    pub content: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum JournalState {
+    Prepared,
+    Committed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Journal {
+    id: String,
+    state: JournalState,
+    entries: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    target: PathBuf,
+    /// Temp file holding the new content; `None` for deletes.
+    temp: Option<PathBuf>,
+    /// Where the current content is moved while the commit is in flight.
+    backup: PathBuf,
+    /// Whether the target existed when the transaction was prepared.
+    existed: bool,
+}
+
+/// Apply `changes` all-or-nothing, returning the hash of each write in order.
+pub(crate) fn commit(root: &Path, id: &str, changes: &[Change]) -> Result<Vec<ContentHash>, PortError> {
+    let journal = prepare(root, id, changes)?;
+    if let Err(e) = journal.entries.iter().try_for_each(apply_entry) {
+        rollback(root, &journal)?;
+        return Err(e);
+    }
+    finish(root, journal)?;
+    Ok(changes
+        .iter()
+        .filter_map(|c| c.content.as_deref().map(ContentHash::from_bytes))
+        .collect())
+}
+
+/// Roll back or finish every journaled transaction, returning the ids rolled back.
+pub(crate) fn recover(root: &Path) -> Result<Vec<String>, PortError> {
+    let dir = root.join(JOURNAL_DIR);
+    let mut paths: Vec<PathBuf> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .map(|e| e.map(|e| e.path()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| port_error(e, &dir))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(port_error(e, &dir)),
+    };
+    paths.sort();
+
+    let mut rolled_back = Vec::new();
+    for path in paths {
+        if path.extension().is_some_and(|ext| ext == "tmp") {
+            // Crashed while writing the journal itself; nothing was applied.
+            remove_if_exists(&path)?;
+            continue;
+        }
+        let content = fs::read(&path).map_err(|e| port_error(e, &path))?;
+        let journal: Journal = serde_json::from_slice(&content).map_err(|e| PortError::Io {
+            message: format!("{}: unreadable transaction journal: {}", path.display(), e),
+        })?;
+        match journal.state {
+            JournalState::Committed => cleanup(root, &journal)?,
+            JournalState::Prepared => {
+                tracing::warn!(transaction = %journal.id, "rolling back interrupted workspace transaction");
+                rollback(root, &journal)?;
+                rolled_back.push(journal.id);
+            }
+        }
+    }
+    Ok(rolled_back)
+}
+
+fn prepare(root: &Path, id: &str, changes: &[Change]) -> Result<Journal, PortError> {
+    for change in changes {
+        if change.target.is_dir() {
+            return Err(PortError::Io {
+                message: format!("{}: is a directory", change.target.display()),
+            });
+        }
+        if change.content.is_none() && !change.target.is_file() {
+            return Err(PortError::FileNotFound {
+                path: change.target.clone(),
+            });
+        }
+    }
+    let journal = Journal {
+        id: id.to_string(),
+        state: JournalState::Prepared,
+        entries: changes
+            .iter()
+            .map(|c| JournalEntry {
+                target: c.target.clone(),
+                temp: c.content.as_ref().map(|_| sibling(&c.target, id, "new")),
+                backup: sibling(&c.target, id, "orig"),
+                existed: c.target.exists(),
+            })
+            .collect(),
+    };
+    write_journal(root, &journal)?;
+
+    let staged = journal.entries.iter().zip(changes).try_for_each(|(entry, change)| {
+        match (&entry.temp, &change.content) {
+            (Some(temp), Some(content)) => write_synced(temp, content),
+            _ => Ok(()),
+        }
+    });
+    if let Err(e) = staged {
+        rollback(root, &journal)?;
+        return Err(e);
+    }
+    Ok(journal)
+}
+
+fn apply_entry(entry: &JournalEntry) -> Result<(), PortError> {
+    if entry.existed {
+        fs::rename(&entry.target, &entry.backup).map_err(|e| port_error(e, &entry.target))?;
+    }
+    if let Some(temp) = &entry.temp {
+        fs::rename(temp, &entry.target).map_err(|e| port_error(e, &entry.target))?;
+    }
+    Ok(())
+}
+
+fn finish(root: &Path, mut journal: Journal) -> Result<(), PortError> {
+    journal.state = JournalState::Committed;
+    write_journal(root, &journal)?;
+    cleanup(root, &journal)
+}
+
+fn cleanup(root: &Path, journal: &Journal) -> Result<(), PortError> {
+    for entry in &journal.entries {
+        remove_if_exists(&entry.backup)?;
+        if let Some(temp) = &entry.temp {
+            remove_if_exists(temp)?;
+        }
+    }
+    remove_if_exists(&journal_path(root, &journal.id))
+}
+
+fn rollback(root: &Path, journal: &Journal) -> Result<(), PortError> {
+    for entry in journal.entries.iter().rev() {
+        if entry.backup.exists() {
+            fs::rename(&entry.backup, &entry.target).map_err(|e| port_error(e, &entry.target))?;
+        } else if !entry.existed && entry.temp.as_ref().is_some_and(|t| !t.exists()) {
+            // The new file was already renamed into place.
+            remove_if_exists(&entry.target)?;
+        }
+        if let Some(temp) = &entry.temp {
+            remove_if_exists(temp)?;
+        }
+    }
+    remove_if_exists(&journal_path(root, &journal.id))
+}
+
+fn write_journal(root: &Path, journal: &Journal) -> Result<(), PortError> {
+    let path = journal_path(root, &journal.id);
+    let temp = path.with_extension("json.tmp");
+    let content = serde_json::to_vec_pretty(journal).map_err(|e| PortError::Internal {
+        message: e.to_string(),
+    })?;
+    write_synced(&temp, &content)?;
+    fs::rename(&temp, &path).map_err(|e| port_error(e, &path))
+}
+
+fn write_synced(path: &Path, content: &[u8]) -> Result<(), PortError> {
+    let write = || -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        file.write_all(content)?;
+        file.sync_all()
+    };
+    write().map_err(|e| port_error(e, path))
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), PortError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(port_error(e, path)),
+        _ => Ok(()),
+    }
+}
+
+fn journal_path(root: &Path, id: &str) -> PathBuf {
+    root.join(JOURNAL_DIR).join(format!("{}.json", id))
+}
+
+/// Hidden file next to `target`, e.g. `.Datasheet.md.<id>.new.tmp`.
+fn sibling(target: &Path, id: &str, kind: &str) -> PathBuf {
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    target.with_file_name(format!(".{}.{}.{}.tmp", name, id, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recover_rolls_back_an_interrupted_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let folder = root.join("PKG-001/DEL-01.01");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("Datasheet.md"), "v1").unwrap();
+        fs::write(folder.join("_STATUS.md"), "OPEN").unwrap();
+        let changes = [
+            Change {
+                target: folder.join("Datasheet.md"),
+                content: Some(b"v2".to_vec()),
+            },
+            Change {
+                target: folder.join("Guidance.md"),
+                content: Some(b"new".to_vec()),
+            },
+            Change {
+                target: folder.join("_STATUS.md"),
+                content: None,
+            },
+        ];
+
+        // Crash after the first two changes were applied.
+        let journal = prepare(root, "tx1", &changes).unwrap();
+        journal.entries[..2].iter().try_for_each(apply_entry).unwrap();
+        assert_eq!(fs::read_to_string(folder.join("Datasheet.md")).unwrap(), "v2");
+
+        assert_eq!(recover(root).unwrap(), ["tx1"]);
+        assert_eq!(fs::read_to_string(folder.join("Datasheet.md")).unwrap(), "v1");
+        assert_eq!(fs::read_to_string(folder.join("_STATUS.md")).unwrap(), "OPEN");
+        let mut names: Vec<_> = fs::read_dir(&folder)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["Datasheet.md", "_STATUS.md"]);
+        assert_eq!(fs::read_dir(root.join(JOURNAL_DIR)).unwrap().count(), 0);
+        assert!(recover(root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn recover_finishes_a_committed_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("a.md"), "old").unwrap();
+        let changes = [Change {
+            target: root.join("a.md"),
+            content: Some(b"new".to_vec()),
+        }];
+
+        let mut journal = prepare(root, "tx2", &changes).unwrap();
+        journal.entries.iter().try_for_each(apply_entry).unwrap();
+        journal.state = JournalState::Committed;
+        write_journal(root, &journal).unwrap();
+
+        assert!(recover(root).unwrap().is_empty());
+        assert_eq!(fs::read_to_string(root.join("a.md")).unwrap(), "new");
+        assert!(!journal.entries[0].backup.exists());
+    }
+}
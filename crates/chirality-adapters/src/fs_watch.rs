@@ -0,0 +1,279 @@
+//! Debounced filesystem watching for the filesystem adapter.
+//!
+//! Raw `notify` events are collected until the subtree has been quiet for the
+//! debounce window, or until the batch has been open for the maximum wait if
+//! it never goes quiet, then coalesced to one event per path. Editors that save by
+//! writing a temp file and renaming it over the original produce a storm of
+//! create/rename/delete events; after coalescing that is a single event on
+//! the document (`Created`, since a rename cannot say whether it replaced an
+//! existing file) and nothing for the temp file.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use chirality_ports::{FsChangeEvent, FsChangeStream, FsChangeType, PortError};
+
+/// Start watching `path` recursively.
+pub(crate) fn watch(path: &Path, debounce: Duration, max_wait: Duration) -> Result<FsChangeStream, PortError> {
+    let (raw_tx, raw_rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+        Ok(event) => {
+            let _ = raw_tx.send(event);
+        }
+        Err(e) => tracing::warn!(error = %e, "filesystem watcher error"),
+    })
+    .map_err(|e| watch_error(e, path))?;
+    watcher
+        .watch(path, RecursiveMode::Recursive)
+        .map_err(|e| watch_error(e, path))?;
+
+    let (tx, rx) = mpsc::channel(256);
+    tokio::spawn(debounce_loop(raw_rx, tx, debounce, max_wait));
+    Ok(Box::pin(WatchStream {
+        events: rx,
+        _watcher: watcher,
+    }))
+}
+
+/// Keeps the watcher alive for as long as the stream is held.
+struct WatchStream {
+    events: mpsc::Receiver<FsChangeEvent>,
+    _watcher: RecommendedWatcher,
+}
+
+impl Stream for WatchStream {
+    type Item = FsChangeEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<FsChangeEvent>> {
+        self.get_mut().events.poll_recv(cx)
+    }
+}
+
+async fn debounce_loop(
+    mut raw: mpsc::UnboundedReceiver<Event>,
+    events: mpsc::Sender<FsChangeEvent>,
+    debounce: Duration,
+    max_wait: Duration,
+) {
+    let mut pending = Coalescer::default();
+    while let Some(first) = raw.recv().await {
+        pending.add(first);
+        let flush_by = Instant::now() + max_wait;
+        let mut closed = false;
+        loop {
+            let quiet_by = (Instant::now() + debounce).min(flush_by);
+            match tokio::time::timeout_at(quiet_by, raw.recv()).await {
+                Ok(Some(event)) => pending.add(event),
+                Ok(None) => {
+                    closed = true;
+                    break;
+                }
+                Err(_) => break,
+            }
+        }
+        for event in pending.drain() {
+            if events.send(event).await.is_err() {
+                return;
+            }
+        }
+        if closed {
+            return;
+        }
+    }
+}
+
+/// Net change per path over one debounce window.
+#[derive(Debug, Default)]
+struct Coalescer {
+    pending: BTreeMap<PathBuf, FsChangeType>,
+}
+
+impl Coalescer {
+    fn add(&mut self, event: Event) {
+        for (path, change) in changes(event) {
+            self.record(path, change);
+        }
+    }
+
+    fn record(&mut self, path: PathBuf, change: FsChangeType) {
+        if is_ignored(&path) {
+            return;
+        }
+        use FsChangeType::*;
+        let net = match (self.pending.get(&path), change) {
+            (None, change) => Some(change),
+            (Some(Created), Deleted) => None,
+            (Some(Created), _) => Some(Created),
+            (Some(Deleted), Created | Modified) => Some(Modified),
+            (Some(_), Deleted) => Some(Deleted),
+            (Some(Modified), _) => Some(Modified),
+        };
+        match net {
+            Some(net) => self.pending.insert(path, net),
+            None => self.pending.remove(&path),
+        };
+    }
+
+    fn drain(&mut self) -> Vec<FsChangeEvent> {
+        std::mem::take(&mut self.pending)
+            .into_iter()
+            .map(|(path, change_type)| FsChangeEvent { path, change_type })
+            .collect()
+    }
+}
+
+fn changes(event: Event) -> Vec<(PathBuf, FsChangeType)> {
+    let tag = |paths: Vec<PathBuf>, change: FsChangeType| -> Vec<_> {
+        paths.into_iter().map(|p| (p, change)).collect()
+    };
+    match event.kind {
+        EventKind::Create(_) => tag(event.paths, FsChangeType::Created),
+        EventKind::Remove(_) => tag(event.paths, FsChangeType::Deleted),
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => tag(event.paths, FsChangeType::Deleted),
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => tag(event.paths, FsChangeType::Created),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            let mut paths = event.paths.into_iter();
+            paths
+                .next()
+                .map(|from| (from, FsChangeType::Deleted))
+                .into_iter()
+                .chain(paths.map(|to| (to, FsChangeType::Created)))
+                .collect()
+        }
+        // Backends that cannot tell the two sides of a rename apart.
+        EventKind::Modify(ModifyKind::Name(_)) => event
+            .paths
+            .into_iter()
+            .map(|p| {
+                let change = if p.exists() {
+                    FsChangeType::Created
+                } else {
+                    FsChangeType::Deleted
+                };
+                (p, change)
+            })
+            .collect(),
+        EventKind::Modify(ModifyKind::Metadata(_)) | EventKind::Access(_) | EventKind::Other => Vec::new(),
+        EventKind::Modify(_) | EventKind::Any => tag(event.paths, FsChangeType::Modified),
+    }
+}
+
+/// `.git/` and `.chirality/` contents, and editor swap, backup and lock files.
+fn is_ignored(path: &Path) -> bool {
+    if path
+        .components()
+        .any(|c| c.as_os_str() == ".git" || c.as_os_str() == ".chirality")
+    {
+        return true;
+    }
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    name.ends_with('~')
+        || name.starts_with(".#")
+        || name.starts_with("~$")
+        || name.starts_with(".~lock.")
+        || name == "4913"
+        || name == ".DS_Store"
+        || [".swp", ".swo", ".swx", ".tmp"].iter().any(|ext| name.ends_with(ext))
+}
+
+fn watch_error(err: notify::Error, path: &Path) -> PortError {
+    match err.kind {
+        notify::ErrorKind::PathNotFound => PortError::FileNotFound {
+            path: path.to_path_buf(),
+        },
+        notify::ErrorKind::Io(e) if e.kind() == std::io::ErrorKind::NotFound => PortError::FileNotFound {
+            path: path.to_path_buf(),
+        },
+        notify::ErrorKind::Io(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            PortError::PermissionDenied {
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            }
+        }
+        kind => PortError::Io {
+            message: format!("watching {}: {:?}", path.display(), kind),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: EventKind, paths: &[&str]) -> Event {
+        let mut event = Event::new(kind);
+        event.paths = paths.iter().map(PathBuf::from).collect();
+        event
+    }
+
+    #[test]
+    fn coalesces_editor_save_and_rename_storms() {
+        let mut pending = Coalescer::default();
+        // Atomic save: write a temp file, rename it over the document.
+        pending.add(event(EventKind::Create(notify::event::CreateKind::File), &["/w/.Datasheet.md.tmp"]));
+        pending.add(event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+            &["/w/.Datasheet.md.tmp", "/w/Datasheet.md"],
+        ));
+        // a -> b -> c, then c edited.
+        pending.add(event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)), &["/w/a.md", "/w/b.md"]));
+        pending.add(event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)), &["/w/b.md", "/w/c.md"]));
+        pending.add(event(EventKind::Modify(ModifyKind::Any), &["/w/c.md"]));
+        // Created and removed within the window.
+        pending.add(event(EventKind::Create(notify::event::CreateKind::File), &["/w/scratch.md"]));
+        pending.add(event(EventKind::Remove(notify::event::RemoveKind::File), &["/w/scratch.md"]));
+        pending.add(event(EventKind::Modify(ModifyKind::Any), &["/w/.git/index", "/w/Guidance.md.swp"]));
+
+        let events: Vec<_> = pending.drain().into_iter().map(|e| (e.path, e.change_type)).collect();
+        assert_eq!(
+            events,
+            [
+                (PathBuf::from("/w/Datasheet.md"), FsChangeType::Created),
+                (PathBuf::from("/w/a.md"), FsChangeType::Deleted),
+                (PathBuf::from("/w/c.md"), FsChangeType::Created),
+            ]
+        );
+        assert!(pending.drain().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn flushes_a_busy_subtree_after_the_maximum_wait() {
+        let (raw_tx, raw_rx) = mpsc::unbounded_channel();
+        let (tx, mut rx) = mpsc::channel(16);
+        tokio::spawn(debounce_loop(raw_rx, tx, Duration::from_millis(100), Duration::from_millis(300)));
+        // An event every 50 ms never leaves the 100 ms debounce window quiet.
+        tokio::spawn(async move {
+            for i in 0..100 {
+                let path = format!("/w/{}.md", i);
+                if raw_tx.send(event(EventKind::Modify(ModifyKind::Any), &[&path])).is_err() {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        });
+
+        let start = Instant::now();
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.path, PathBuf::from("/w/0.md"));
+        assert!(start.elapsed() <= Duration::from_millis(350), "{:?}", start.elapsed());
+    }
+
+    #[test]
+    fn ignores_git_and_editor_temp_files() {
+        for path in ["/w/.git/HEAD", "/w/a.md~", "/w/.#a.md", "/w/.a.md.swp", "/w/~$Spec.docx", "/w/4913"] {
+            assert!(is_ignored(Path::new(path)), "{}", path);
+        }
+        assert!(!is_ignored(Path::new("/w/PKG-001/Datasheet.md")));
+    }
+}
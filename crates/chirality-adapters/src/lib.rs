@@ -11,8 +11,13 @@
 //! - **ClaudeApiAdapter**: AgentExecutorPort implementation
 //! - **ZitadelAdapter**: IdentityPort implementation (from solver-ralph)
 
+pub mod filesystem;
+mod fs_transaction;
+mod fs_watch;
+
+pub use filesystem::FilesystemAdapter;
+
 // Adapters will be implemented in Phase 3
-// pub mod filesystem;
 // pub mod git2_adapter;
 // pub mod minio;
 // pub mod claude_api;
@@ -0,0 +1,159 @@
+//! Agent registry - definitions loaded from a folder of `AGENT_*.md` files.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chirality_domain::{AgentDefinition, BriefSchemas};
+use chirality_ports::WorkspacePort;
+
+use crate::error::AppError;
+
+/// Agent definitions keyed by agent name.
+#[derive(Debug, Clone, Default)]
+pub struct AgentRegistry {
+    definitions: BTreeMap<String, AgentDefinition>,
+}
+
+impl AgentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `AGENT_*.md` file directly inside `dir`.
+    ///
+    /// Other files are ignored. The first file that fails to parse aborts the
+    /// load with an error naming the file and line.
+    pub async fn load<W: WorkspacePort + ?Sized>(
+        workspace: &W,
+        dir: &Path,
+    ) -> Result<Self, AppError> {
+        let mut entries = workspace.list_dir(dir).await?;
+        entries.sort();
+        let mut registry = Self::new();
+        for path in entries {
+            let Some(file_name) = path.file_name().map(|n| n.to_string_lossy().into_owned())
+            else {
+                continue;
+            };
+            if AgentDefinition::name_from_filename(&file_name).is_none()
+                || workspace.is_dir(&path).await?
+            {
+                continue;
+            }
+            let content = workspace.read(&path).await?;
+            registry.insert(AgentDefinition::parse(
+                &file_name,
+                &String::from_utf8_lossy(&content),
+            )?);
+        }
+        Ok(registry)
+    }
+
+    /// Add or replace a definition.
+    pub fn insert(&mut self, definition: AgentDefinition) {
+        self.definitions.insert(definition.name.clone(), definition);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AgentDefinition> {
+        self.definitions.get(name)
+    }
+
+    /// Definitions in name order.
+    pub fn iter(&self) -> impl Iterator<Item = &AgentDefinition> {
+        self.definitions.values()
+    }
+
+    /// Built-in brief schemas overlaid with those declared by each definition.
+    ///
+    /// A definition whose header declares no schema keys keeps the built-in
+    /// schema for its name.
+    pub fn brief_schemas(&self) -> BriefSchemas {
+        let mut schemas = BriefSchemas::builtin();
+        for definition in self.iter() {
+            if let Some(schema) = &definition.brief_schema {
+                schemas.insert(definition.name.clone(), schema.clone());
+            }
+        }
+        schemas
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use chirality_domain::{AgentClass, DomainError, InputRule};
+
+    #[tokio::test]
+    async fn loads_agent_files_and_skips_others() {
+        let workspace = InMemoryWorkspace::new()
+            .with_file(
+                "/agents/AGENT_4_DOCUMENTS.md",
+                "AGENT_TYPE: 2\nAGENT_CLASS: TASK\nWRITE_SCOPE: deliverable-local\n---\n",
+            )
+            .with_file(
+                "/agents/AGENT_ORCHESTRATOR.md",
+                "AGENT_TYPE: 1\nAGENT_CLASS: PERSONA\nWRITE_SCOPE: none\n---\n",
+            )
+            .with_file("/agents/README.md", "not an agent");
+        let registry = AgentRegistry::load(&workspace, Path::new("/agents"))
+            .await
+            .unwrap();
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            registry.get("ORCHESTRATOR").unwrap().agent_class,
+            AgentClass::Persona
+        );
+        assert!(registry.get("README").is_none());
+    }
+
+    #[tokio::test]
+    async fn reports_file_and_line_of_bad_header() {
+        let workspace = InMemoryWorkspace::new().with_file(
+            "/agents/AGENT_BROKEN.md",
+            "AGENT_TYPE: 2\nAGENT_CLASS: TASK\nWRITE_SCOPE: everywhere\n",
+        );
+        let result = AgentRegistry::load(&workspace, Path::new("/agents")).await;
+        assert!(matches!(
+            result,
+            Err(AppError::Domain(DomainError::InvalidAgentDefinition { ref file, line: 3, .. }))
+                if file == "AGENT_BROKEN.md"
+        ));
+    }
+
+    #[tokio::test]
+    async fn brief_schemas_keep_builtin_unless_declared() {
+        let workspace = InMemoryWorkspace::new()
+            .with_file(
+                "/agents/AGENT_4_DOCUMENTS.md",
+                "AGENT_TYPE: 2\nAGENT_CLASS: TASK\nWRITE_SCOPE: deliverable-local\n---\n",
+            )
+            .with_file(
+                "/agents/AGENT_AGGREGATION.md",
+                "AGENT_TYPE: 2\nAGENT_CLASS: TASK\nWRITE_SCOPE: none\nREQUIRED_INPUTS: project_id\n---\n",
+            );
+        let registry = AgentRegistry::load(&workspace, Path::new("/agents"))
+            .await
+            .unwrap();
+        let schemas = registry.brief_schemas();
+
+        assert_eq!(
+            schemas.get("4_DOCUMENTS"),
+            BriefSchemas::builtin().get("4_DOCUMENTS")
+        );
+        assert!(!schemas.get("4_DOCUMENTS").unwrap().inputs.is_empty());
+        assert_eq!(
+            schemas.get("AGGREGATION").unwrap().inputs,
+            [InputRule::required(&["project_id"])]
+        );
+    }
+}
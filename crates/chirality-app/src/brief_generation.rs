@@ -0,0 +1,111 @@
+//! Brief generation - drafts TASK briefs from what a deliverable folder holds.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chirality_domain::{
+    AgentDefinition, BriefBuilder, BriefTemplates, Deliverable, DocumentType, SessionBrief,
+};
+use chirality_ports::{PortError, WorkspacePort};
+
+use crate::error::AppError;
+
+/// Generates briefs from a deliverable's `_CONTEXT.md` and `_REFERENCES.md`.
+pub struct BriefGenerator<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+    templates: BriefTemplates,
+}
+
+impl<W: WorkspacePort + ?Sized> BriefGenerator<W> {
+    /// A generator using the built-in templates.
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self {
+            workspace,
+            templates: BriefTemplates::builtin(),
+        }
+    }
+
+    /// Overlay human-maintained templates on the built-in ones.
+    pub fn with_templates(mut self, templates: BriefTemplates) -> Self {
+        self.templates.merge(templates);
+        self
+    }
+
+    /// Overlay templates from a JSON file in the workspace.
+    pub async fn load_templates(mut self, path: &Path) -> Result<Self, AppError> {
+        let content = self.workspace.read(path).await?;
+        self.templates
+            .merge(BriefTemplates::from_json(&String::from_utf8_lossy(&content))?);
+        Ok(self)
+    }
+
+    /// Draft a brief for `agent` to work on `deliverable`.
+    ///
+    /// Missing `_CONTEXT.md` or `_REFERENCES.md` files are left out of the inputs.
+    pub async fn generate(
+        &self,
+        agent: &AgentDefinition,
+        deliverable: &Deliverable,
+    ) -> Result<SessionBrief, AppError> {
+        let mut builder = BriefBuilder::new(agent, &self.templates);
+        if let Some(context) = self.read_optional(deliverable, DocumentType::Context).await? {
+            builder = builder.with_context(context);
+        }
+        if let Some(references) = self.read_optional(deliverable, DocumentType::References).await? {
+            builder = builder.with_references(references);
+        }
+        Ok(builder.build(deliverable))
+    }
+
+    async fn read_optional(
+        &self,
+        deliverable: &Deliverable,
+        document_type: DocumentType,
+    ) -> Result<Option<String>, AppError> {
+        let path = deliverable.folder_path.join(document_type.filename());
+        match self.workspace.read(&path).await {
+            Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+            Err(PortError::FileNotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use chirality_domain::PackageId;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn generates_brief_from_folder_and_template_file() {
+        let folder = "/proj/PKG-001_Civil/DEL-01.01_Foundation";
+        let workspace = InMemoryWorkspace::new()
+            .with_file(format!("{}/_CONTEXT.md", folder), "Spread footing on clay.\n")
+            .with_file(format!("{}/_REFERENCES.md", folder), "- GR-12\n")
+            .with_file(
+                "/proj/.chirality/brief_templates.json",
+                r#"{ "4_DOCUMENTS": { "task_definition": "Draft {label}" } }"#,
+            );
+        let generator = BriefGenerator::new(Arc::new(workspace))
+            .load_templates(Path::new("/proj/.chirality/brief_templates.json"))
+            .await
+            .unwrap();
+        let agent = AgentDefinition::parse(
+            "AGENT_4_DOCUMENTS.md",
+            "AGENT_TYPE: 2\nAGENT_CLASS: TASK\nWRITE_SCOPE: deliverable-local\n",
+        )
+        .unwrap();
+        let deliverable =
+            Deliverable::new(PackageId::from_legacy(1), "Foundation", PathBuf::from(folder))
+                .with_legacy_id(1, 1);
+
+        let brief = generator.generate(&agent, &deliverable).await.unwrap();
+        assert_eq!(brief.task_definition, "Draft Foundation");
+        assert_eq!(brief.output_contract.len(), 4);
+        assert_eq!(brief.inputs["context"], "Spread footing on clay.");
+        assert_eq!(brief.inputs["references"], json!(["GR-12"]));
+    }
+}
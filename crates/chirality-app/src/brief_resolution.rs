@@ -0,0 +1,302 @@
+//! Brief resolution - binds a brief's `inputs` to entities in the workspace.
+//!
+//! Schema validation only checks that `deliverable_id` is a string; this step
+//! checks that it names a deliverable that exists and can be worked on, and
+//! derives the session scope and write scope from it so callers don't have
+//! to assemble them by hand.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chirality_domain::{
+    ActorId, AgentSession, Deliverable, DeliverableFolder, DeliverableId, DomainError, FolderName,
+    Package, PackageFolder, PackageId, Project, ProjectId, SessionBrief, SessionScope, WriteScope,
+    WriteScopeTemplate,
+};
+use chirality_ports::WorkspacePort;
+
+use crate::error::AppError;
+use crate::workspace_discovery::WorkspaceScanner;
+
+/// A brief whose `inputs` name entities that exist in the workspace.
+#[derive(Debug, Clone)]
+pub struct ResolvedBrief {
+    pub brief: SessionBrief,
+    /// Narrowest scope named by the inputs (project if none is named).
+    pub scope: SessionScope,
+    pub package: Option<Package>,
+    pub deliverable: Option<Deliverable>,
+    /// `DeliverableLocal` for deliverable briefs, `None` otherwise.
+    pub write_scope: WriteScope,
+    pub workspace_root: PathBuf,
+}
+
+impl ResolvedBrief {
+    /// Instantiate an agent's write scope template for this brief.
+    pub fn write_scope_from(&self, template: &WriteScopeTemplate) -> Result<WriteScope, DomainError> {
+        let deliverable = self
+            .deliverable
+            .as_ref()
+            .map(|d| (&d.id, d.folder_path.as_path()));
+        template.instantiate(&self.workspace_root, deliverable)
+    }
+
+    /// Create a TASK session for this brief.
+    pub fn into_task_session(self, agent_name: impl Into<String>, started_by: ActorId) -> AgentSession {
+        AgentSession::new_task(agent_name, self.brief, self.scope, self.write_scope, started_by)
+    }
+}
+
+/// Resolves brief inputs against a project's workspace.
+pub struct BriefResolver<W: WorkspacePort + ?Sized> {
+    scanner: WorkspaceScanner<W>,
+}
+
+impl<W: WorkspacePort + ?Sized> BriefResolver<W> {
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self {
+            scanner: WorkspaceScanner::new(workspace),
+        }
+    }
+
+    /// Resolve `project_id`, `package_id` and `deliverable_id` in `brief.inputs`.
+    ///
+    /// Legacy ids are canonicalized the way folder names are, so `DEL-1.01`
+    /// finds the `DEL-01.01` folder. Fails with `NotFound` for ids the workspace does not contain, and with
+    /// `PreconditionFailed` if the deliverable's state does not allow work.
+    pub async fn resolve(&self, project: &Project, brief: SessionBrief) -> Result<ResolvedBrief, AppError> {
+        let project_id = input_id(&brief, "project_id")?.map(ProjectId::from_string);
+        let package_id = input_id(&brief, "package_id")?.map(canonical_package_id);
+        let deliverable_id = input_id(&brief, "deliverable_id")?.map(canonical_deliverable_id);
+
+        if let Some(id) = &project_id {
+            if id != &project.id {
+                return Err(not_found("Project", id.as_str()).into());
+            }
+        }
+
+        let snapshot = self.scanner.scan_project(project.clone()).await?;
+
+        let package = match &package_id {
+            Some(id) => Some(
+                snapshot
+                    .package(id)
+                    .cloned()
+                    .ok_or_else(|| not_found("Package", id.as_str()))?,
+            ),
+            None => None,
+        };
+
+        let deliverable = match &deliverable_id {
+            Some(id) => {
+                let deliverable = snapshot
+                    .deliverable(id)
+                    .cloned()
+                    .ok_or_else(|| not_found("Deliverable", id.as_str()))?;
+                if let Some(package_id) = &package_id {
+                    if &deliverable.package_id != package_id {
+                        return Err(DomainError::InvalidBrief {
+                            reason: format!(
+                                "deliverable {} belongs to {}, not {}",
+                                deliverable.id, deliverable.package_id, package_id
+                            ),
+                        }
+                        .into());
+                    }
+                }
+                if !deliverable.state.allows_work() {
+                    return Err(DomainError::PreconditionFailed {
+                        message: format!(
+                            "{} is {}; briefs can only target deliverables open for work",
+                            deliverable.id, deliverable.state
+                        ),
+                    }
+                    .into());
+                }
+                Some(deliverable)
+            }
+            None => None,
+        };
+
+        let (scope, write_scope) = match (&deliverable, &package) {
+            (Some(d), _) => (
+                SessionScope::Deliverable {
+                    deliverable_id: d.id.clone(),
+                },
+                WriteScope::DeliverableLocal {
+                    deliverable_id: d.id.clone(),
+                    deliverable_path: d.folder_path.clone(),
+                },
+            ),
+            (None, Some(p)) => (
+                SessionScope::Package {
+                    package_id: p.id.clone(),
+                },
+                WriteScope::None,
+            ),
+            (None, None) => (
+                SessionScope::Project {
+                    project_id: project.id.clone(),
+                },
+                WriteScope::None,
+            ),
+        };
+        let package = package.or_else(|| {
+            deliverable
+                .as_ref()
+                .and_then(|d| snapshot.package(&d.package_id).cloned())
+        });
+
+        Ok(ResolvedBrief {
+            brief,
+            scope,
+            package,
+            deliverable,
+            write_scope,
+            workspace_root: project.workspace_path.clone(),
+        })
+    }
+}
+
+/// A string id from `brief.inputs`, if present.
+fn input_id(brief: &SessionBrief, key: &str) -> Result<Option<String>, DomainError> {
+    match brief.inputs.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(id)) if !id.trim().is_empty() => Ok(Some(id.trim().to_string())),
+        Some(other) => Err(DomainError::InvalidBrief {
+            reason: format!("inputs.{} must be a non-empty string, found {}", key, other),
+        }),
+    }
+}
+
+/// `PKG-1` → `PKG-001`; anything that is not a bare package id is kept as given.
+fn canonical_package_id(id: String) -> PackageId {
+    match FolderName::parse(&id) {
+        FolderName::Package(PackageFolder { id, label: None, .. }) => id,
+        _ => PackageId::from_string(id),
+    }
+}
+
+/// `DEL-1.01` → `DEL-01.01`; anything that is not a bare deliverable id is kept as given.
+fn canonical_deliverable_id(id: String) -> DeliverableId {
+    match FolderName::parse(&id) {
+        FolderName::Deliverable(DeliverableFolder { id, label: None, .. }) => id,
+        _ => DeliverableId::from_string(id),
+    }
+}
+
+fn not_found(entity_type: &str, id: &str) -> DomainError {
+    DomainError::NotFound {
+        entity_type: entity_type.to_string(),
+        id: id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use serde_json::json;
+    use std::path::Path;
+
+    fn project() -> Project {
+        Project::new("proj", PathBuf::from("/proj"), ActorId::system())
+    }
+
+    fn resolver() -> BriefResolver<InMemoryWorkspace> {
+        let workspace = InMemoryWorkspace::new()
+            .with_file(
+                "/proj/PKG-001_Civil/DEL-01.01_Foundation/_STATUS.md",
+                "<!-- chirality:status:begin -->\n- **State:** IN_PROGRESS\n<!-- chirality:status:end -->\n",
+            )
+            .with_file(
+                "/proj/PKG-001_Civil/DEL-01.02_Walls/_STATUS.md",
+                "<!-- chirality:status:begin -->\n- **State:** ISSUED\n<!-- chirality:status:end -->\n",
+            )
+            .with_dir("/proj/PKG-002_Mechanical");
+        BriefResolver::new(Arc::new(workspace))
+    }
+
+    fn brief(inputs: serde_json::Value) -> SessionBrief {
+        SessionBrief {
+            task_definition: "Generate docs".to_string(),
+            scope_description: String::new(),
+            output_contract: vec![],
+            constraints: vec![],
+            success_criteria: vec![],
+            inputs,
+        }
+    }
+
+    #[tokio::test]
+    async fn fills_scope_and_write_scope_from_deliverable() {
+        let resolved = resolver()
+            .resolve(&project(), brief(json!({ "deliverable_id": "DEL-01.01" })))
+            .await
+            .unwrap();
+
+        let folder = Path::new("/proj/PKG-001_Civil/DEL-01.01_Foundation");
+        assert!(matches!(
+            &resolved.scope,
+            SessionScope::Deliverable { deliverable_id } if deliverable_id.as_str() == "DEL-01.01"
+        ));
+        assert!(matches!(
+            &resolved.write_scope,
+            WriteScope::DeliverableLocal { deliverable_path, .. } if deliverable_path == folder
+        ));
+        assert_eq!(resolved.package.as_ref().unwrap().label, "Civil");
+
+        let session = resolved.into_task_session("4_DOCUMENTS", ActorId::human("alice"));
+        assert!(matches!(session.scope, SessionScope::Deliverable { .. }));
+    }
+
+    #[tokio::test]
+    async fn canonicalizes_legacy_ids() {
+        let resolved = resolver()
+            .resolve(
+                &project(),
+                brief(json!({ "package_id": "PKG-1", "deliverable_id": "DEL-1.01" })),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(resolved.deliverable.unwrap().id.as_str(), "DEL-01.01");
+        assert_eq!(resolved.package.unwrap().id.as_str(), "PKG-001");
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_mismatched_and_closed_entities() {
+        let resolver = resolver();
+        let project = project();
+        let resolve = |inputs| resolver.resolve(&project, brief(inputs));
+
+        assert!(matches!(
+            resolve(json!({ "deliverable_id": "DEL-09.99" })).await,
+            Err(AppError::Domain(DomainError::NotFound { ref id, .. })) if id == "DEL-09.99"
+        ));
+        assert!(matches!(
+            resolve(json!({ "package_id": "PKG-007" })).await,
+            Err(AppError::Domain(DomainError::NotFound { .. }))
+        ));
+        assert!(matches!(
+            resolve(json!({ "project_id": "proj:other" })).await,
+            Err(AppError::Domain(DomainError::NotFound { .. }))
+        ));
+        assert!(matches!(
+            resolve(json!({ "package_id": "PKG-002", "deliverable_id": "DEL-01.01" })).await,
+            Err(AppError::Domain(DomainError::InvalidBrief { .. }))
+        ));
+        assert!(matches!(
+            resolve(json!({ "deliverable_id": "DEL-01.02" })).await,
+            Err(AppError::Domain(DomainError::PreconditionFailed { .. }))
+        ));
+        assert!(matches!(
+            resolve(json!({ "deliverable_id": 101 })).await,
+            Err(AppError::Domain(DomainError::InvalidBrief { .. }))
+        ));
+
+        let package = resolve(json!({ "package_id": "PKG-002" })).await.unwrap();
+        assert!(matches!(package.scope, SessionScope::Package { .. }));
+        assert!(matches!(package.write_scope, WriteScope::None));
+    }
+}
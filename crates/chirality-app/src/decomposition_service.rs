@@ -0,0 +1,234 @@
+//! Decomposition service - keeps the folder tree in step with the decomposition.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chirality_domain::{
+    ActorId, CommitHash, Decomposition, DeliverableId, DomainError, Drift, PackageId,
+    PartitionIssue, Project, ScopePartition,
+};
+use chirality_ports::{GitPort, WorkspacePort};
+
+use crate::error::AppError;
+use crate::workspace_discovery::WorkspaceScanner;
+
+/// Outcome of materialising a decomposition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaterialiseReport {
+    pub created_packages: Vec<PackageId>,
+    pub scaffolded: Vec<DeliverableId>,
+    /// Drift that remains after scaffolding (undeclared, misplaced or
+    /// relabelled folders); the runtime never moves or deletes folders.
+    pub drift: Vec<Drift>,
+}
+
+/// Reads `Project.decomposition_path` and reconciles it with the workspace.
+pub struct DecompositionService<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+}
+
+impl<W: WorkspacePort + ?Sized> DecompositionService<W> {
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self { workspace }
+    }
+
+    /// Parse the project's decomposition document.
+    ///
+    /// A relative `decomposition_path` is resolved against the workspace root.
+    pub async fn load(&self, project: &Project) -> Result<Decomposition, AppError> {
+        let path = self.decomposition_path(project)?;
+        let content = self.workspace.read(&path).await?;
+        Ok(Decomposition::parse(project, &String::from_utf8_lossy(&content))?)
+    }
+
+    /// Differences between the decomposition and the current folder tree.
+    pub async fn drift(&self, project: &Project) -> Result<Vec<Drift>, AppError> {
+        let decomposition = self.load(project).await?;
+        self.drift_from(project, &decomposition).await
+    }
+
+    async fn drift_from(
+        &self,
+        project: &Project,
+        decomposition: &Decomposition,
+    ) -> Result<Vec<Drift>, AppError> {
+        let snapshot = WorkspaceScanner::new(self.workspace.clone())
+            .scan_project(project.clone())
+            .await?;
+        Ok(decomposition.drift(
+            &snapshot.packages,
+            &snapshot.deliverables,
+            &project.workspace_path,
+        ))
+    }
+
+    /// Scope partition issues in the decomposition document.
+    pub async fn validate_partition(&self, project: &Project) -> Result<Vec<PartitionIssue>, AppError> {
+        Ok(self.load(project).await?.partition_issues())
+    }
+
+    /// Commit the decomposition document, refusing if the packages do not
+    /// partition the scope of work.
+    pub async fn commit<G: GitPort + ?Sized>(
+        &self,
+        git: &G,
+        project: &Project,
+        author: &ActorId,
+        message: &str,
+    ) -> Result<CommitHash, AppError> {
+        let decomposition = self.load(project).await?;
+        ScopePartition::validate(&decomposition.packages, &decomposition.sow_items)?;
+        git.stage(&[self.decomposition_path(project)?]).await?;
+        Ok(git.commit(message, author).await?)
+    }
+
+    /// Create folders for every declared package and deliverable that lacks one.
+    ///
+    /// Deliverables of a package that already exists are scaffolded inside
+    /// its current folder, so a relabelled package never gets a second tree.
+    pub async fn materialise(&self, project: &Project) -> Result<MaterialiseReport, AppError> {
+        let decomposition = self.load(project).await?;
+        let mut report = MaterialiseReport::default();
+        for drift in self.drift_from(project, &decomposition).await? {
+            match drift {
+                Drift::MissingPackage { package_id, path } => {
+                    self.workspace.create_dir_all(&path).await?;
+                    report.created_packages.push(package_id);
+                }
+                Drift::MissingDeliverable { deliverable_id, path } => {
+                    let mut deliverable = decomposition
+                        .deliverables
+                        .iter()
+                        .find(|d| d.id == deliverable_id)
+                        .expect("drift only names declared deliverables")
+                        .clone();
+                    deliverable.folder_path = path;
+                    self.workspace.scaffold_deliverable(&deliverable).await?;
+                    report.scaffolded.push(deliverable_id);
+                }
+                other => report.drift.push(other),
+            }
+        }
+        Ok(report)
+    }
+
+    fn decomposition_path(&self, project: &Project) -> Result<PathBuf, DomainError> {
+        let path = project
+            .decomposition_path
+            .as_ref()
+            .ok_or_else(|| DomainError::PreconditionFailed {
+                message: format!("project {} has no decomposition document", project.name),
+            })?;
+        Ok(project.workspace_path.join(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use crate::test_support::{InMemoryWorkspace, RecordingGit};
+    use chirality_domain::ActorId;
+
+    const DECOMPOSITION: &str = "## PKG-001 Civil\n- Foundations\n\n\
+        ### DEL-01.01 Foundation\n- Type: Design Report\n\n\
+        ### DEL-01.02 Grading\n\n\
+        ## PKG-002 Mechanical\n";
+
+    #[tokio::test]
+    async fn materialise_scaffolds_missing_folders_and_reports_drift() {
+        let workspace = Arc::new(
+            InMemoryWorkspace::new()
+                .with_file("/proj/_DECOMPOSITION.md", DECOMPOSITION)
+                .with_file("/proj/PKG-001_Civil/DEL-01.01_Foundation/Datasheet.md", "# DS")
+                .with_dir("/proj/PKG-001_Civil/DEL-01.07_Extra"),
+        );
+        let project = Project::new("proj", PathBuf::from("/proj"), ActorId::system())
+            .with_decomposition(PathBuf::from("_DECOMPOSITION.md"));
+        let service = DecompositionService::new(workspace.clone());
+
+        let report = service.materialise(&project).await.unwrap();
+        assert_eq!(report.created_packages, [PackageId::from_legacy(2)]);
+        assert_eq!(report.scaffolded, [DeliverableId::from_legacy(1, 2)]);
+        assert_eq!(
+            report.drift,
+            [Drift::UndeclaredDeliverable {
+                deliverable_id: DeliverableId::from_legacy(1, 7),
+                path: PathBuf::from("/proj/PKG-001_Civil/DEL-01.07_Extra"),
+            }]
+        );
+        assert!(workspace
+            .contents("/proj/PKG-001_Civil/DEL-01.02_Grading/_STATUS.md")
+            .is_some());
+        assert_eq!(workspace.contents("/proj/PKG-001_Civil/DEL-01.01_Foundation/Datasheet.md").unwrap(), "# DS");
+
+        let again = service.materialise(&project).await.unwrap();
+        assert!(again.scaffolded.is_empty() && again.created_packages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn materialise_uses_the_existing_package_folder() {
+        let workspace = Arc::new(
+            InMemoryWorkspace::new()
+                .with_file("/proj/_DECOMPOSITION.md", DECOMPOSITION)
+                .with_file("/proj/PKG-001_Civil_Works/DEL-01.01_Foundation/Datasheet.md", "# DS")
+                .with_dir("/proj/PKG-002_Mechanical"),
+        );
+        let project = Project::new("proj", PathBuf::from("/proj"), ActorId::system())
+            .with_decomposition(PathBuf::from("_DECOMPOSITION.md"));
+        let service = DecompositionService::new(workspace.clone());
+
+        let report = service.materialise(&project).await.unwrap();
+        assert!(report.created_packages.is_empty());
+        assert_eq!(report.scaffolded, [DeliverableId::from_legacy(1, 2)]);
+        assert!(matches!(report.drift.as_slice(), [Drift::LabelMismatch { id, .. }] if id == "PKG-001"));
+        assert!(workspace
+            .contents("/proj/PKG-001_Civil_Works/DEL-01.02_Grading/_STATUS.md")
+            .is_some());
+        assert!(!workspace.exists(Path::new("/proj/PKG-001_Civil")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn load_requires_a_decomposition_path() {
+        let service = DecompositionService::new(Arc::new(InMemoryWorkspace::new()));
+        let project = Project::new("proj", PathBuf::from("/proj"), ActorId::system());
+        assert!(matches!(
+            service.load(&project).await,
+            Err(AppError::Domain(DomainError::PreconditionFailed { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn commit_is_blocked_by_partition_issues() {
+        let overlapping = "## Scope of Work\n- Foundations\n- HVAC\n\n\
+            ## PKG-001 Civil\n- Foundations\n\n\
+            ## PKG-002 Mechanical\n- Foundations\n";
+        let workspace = Arc::new(InMemoryWorkspace::new().with_file("/proj/_DECOMPOSITION.md", overlapping));
+        let project = Project::new("proj", PathBuf::from("/proj"), ActorId::system())
+            .with_decomposition(PathBuf::from("_DECOMPOSITION.md"));
+        let service = DecompositionService::new(workspace.clone());
+        let git = RecordingGit::new();
+
+        assert_eq!(service.validate_partition(&project).await.unwrap().len(), 2);
+        let err = service.commit(&git, &project, &ActorId::system(), "Update decomposition").await;
+        assert!(matches!(
+            err,
+            Err(AppError::Domain(DomainError::ScopePartitionViolations { .. }))
+        ));
+        assert!(git.commits().is_empty());
+
+        workspace
+            .write(
+                Path::new("/proj/_DECOMPOSITION.md"),
+                b"## SOW\n- Foundations\n- HVAC\n\n## PKG-001 Civil\n- Foundations\n\n## PKG-002 Mechanical\n- HVAC\n",
+            )
+            .await
+            .unwrap();
+        service
+            .commit(&git, &project, &ActorId::system(), "Update decomposition")
+            .await
+            .unwrap();
+        assert_eq!(git.staged(), [PathBuf::from("/proj/_DECOMPOSITION.md")]);
+        assert_eq!(git.commits(), ["Update decomposition"]);
+    }
+}
@@ -0,0 +1,404 @@
+//! Deliverable service - lifecycle transitions persisted to `_STATUS.md`.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chirality_domain::{
+    ActorId, Deliverable, DeliverableState, Document, DocumentType, DomainError, ReadinessChecks,
+    ReadinessInput, ReadinessReport, RevisitNote, StatusFile,
+};
+use chirality_ports::{PortError, WorkspacePort};
+
+use crate::error::AppError;
+
+/// Manages deliverable lifecycle against the workspace.
+pub struct DeliverableService<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+}
+
+impl<W: WorkspacePort + ?Sized> DeliverableService<W> {
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self { workspace }
+    }
+
+    /// Read and parse the deliverable's `_STATUS.md`.
+    ///
+    /// A missing file yields a fresh status in the deliverable's current state.
+    pub async fn load_status(&self, deliverable: &Deliverable) -> Result<StatusFile, AppError> {
+        let path = deliverable.folder_path.join(DocumentType::Status.filename());
+        match self.workspace.read(&path).await {
+            Ok(bytes) => Ok(StatusFile::parse(&String::from_utf8_lossy(&bytes))?),
+            Err(PortError::FileNotFound { .. }) => Ok(StatusFile::new(deliverable.state)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Transition a deliverable and rewrite the managed block of `_STATUS.md`.
+    ///
+    /// Fails if the state on disk no longer matches the in-memory deliverable,
+    /// so a transition never silently overrides a human edit.
+    pub async fn transition(
+        &self,
+        deliverable: &mut Deliverable,
+        target: DeliverableState,
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> Result<(), AppError> {
+        let mut status = self.load_status(deliverable).await?;
+        self.ensure_in_sync(deliverable, &status)?;
+
+        // Apply to a copy so a failed write leaves the deliverable untouched.
+        let mut updated = deliverable.clone();
+        let transition = updated.transition(target, actor, reason)?.clone();
+        status.record_transition(transition);
+        self.write_status(&updated, &status).await?;
+        *deliverable = updated;
+        Ok(())
+    }
+
+    /// Run readiness checks against the deliverable folder as it is on disk.
+    pub async fn readiness(
+        &self,
+        deliverable: &Deliverable,
+        checks: &ReadinessChecks,
+    ) -> Result<ReadinessReport, AppError> {
+        let input = self.readiness_input(deliverable).await?;
+        Ok(checks.run(&input))
+    }
+
+    /// Move an IN_PROGRESS deliverable to CHECKING if the folder passes `checks`.
+    pub async fn submit_for_checking(
+        &self,
+        deliverable: &mut Deliverable,
+        checks: &ReadinessChecks,
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> Result<(), AppError> {
+        let mut status = self.load_status(deliverable).await?;
+        self.ensure_in_sync(deliverable, &status)?;
+
+        let snapshot = deliverable.clone();
+        let input = self.readiness_input(&snapshot).await?;
+        let mut updated = deliverable.clone();
+        let transition = updated
+            .submit_for_checking(checks, &input, actor, reason)?
+            .clone();
+        status.record_transition(transition);
+        self.write_status(&updated, &status).await?;
+        *deliverable = updated;
+        Ok(())
+    }
+
+    async fn readiness_input<'a>(
+        &self,
+        deliverable: &'a Deliverable,
+    ) -> Result<ReadinessInput<'a>, AppError> {
+        let root = &deliverable.folder_path;
+        let mut input = ReadinessInput::new(deliverable);
+        let mut pending = vec![root.clone()];
+        while let Some(dir) = pending.pop() {
+            for entry in self.workspace.list_dir(&dir).await? {
+                if self.workspace.is_dir(&entry).await? {
+                    pending.push(entry);
+                } else if let Ok(relative) = entry.strip_prefix(root) {
+                    input.files.push(PathBuf::from(relative));
+                }
+            }
+        }
+        for document_type in DocumentType::ALL {
+            let path = root.join(document_type.filename());
+            if input.files.iter().any(|f| f.as_os_str() == document_type.filename()) {
+                let bytes = self.workspace.read(&path).await?;
+                input
+                    .documents
+                    .push((document_type, String::from_utf8_lossy(&bytes).into_owned()));
+            }
+        }
+        Ok(input)
+    }
+
+    /// Issue a deliverable under review along with its four core documents.
+    ///
+    /// `documents` must hold the deliverable's core documents, all REVIEWED;
+    /// they are updated to ISSUED only if `_STATUS.md` is written.
+    pub async fn issue(
+        &self,
+        deliverable: &mut Deliverable,
+        documents: &mut [Document],
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> Result<(), AppError> {
+        self.apply_with_documents(deliverable, documents, |d, docs| {
+            d.issue(docs, actor, reason).map(|_| ())
+        })
+        .await
+    }
+
+    /// Reopen an ISSUED deliverable as its next revision and persist it.
+    ///
+    /// `documents` must hold the deliverable's four core documents; they are
+    /// returned to DRAFT under the new revision. The frozen issued revision is
+    /// appended to `_STATUS.md` alongside the transition.
+    pub async fn revise(
+        &self,
+        deliverable: &mut Deliverable,
+        documents: &mut [Document],
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> Result<(), AppError> {
+        self.apply_with_documents(deliverable, documents, |d, docs| {
+            d.revise(docs, actor, reason).map(|_| ())
+        })
+        .await
+    }
+
+    /// Run a lifecycle operation that also touches documents, persisting the
+    /// resulting transition and revision record before committing either.
+    async fn apply_with_documents<F>(
+        &self,
+        deliverable: &mut Deliverable,
+        documents: &mut [Document],
+        operation: F,
+    ) -> Result<(), AppError>
+    where
+        F: FnOnce(&mut Deliverable, &mut [Document]) -> Result<(), DomainError>,
+    {
+        let mut status = self.load_status(deliverable).await?;
+        self.ensure_in_sync(deliverable, &status)?;
+
+        let mut updated = deliverable.clone();
+        let mut updated_documents = documents.to_vec();
+        operation(&mut updated, &mut updated_documents)?;
+        let transition = updated
+            .history()
+            .last()
+            .expect("lifecycle operations record a transition")
+            .clone();
+        status.record_transition(transition);
+        status.revision = Some(updated.revision.clone());
+        status.issued_revisions = updated.issued_revisions().to_vec();
+        self.write_status(&updated, &status).await?;
+        *deliverable = updated;
+        documents.clone_from_slice(&updated_documents);
+        Ok(())
+    }
+
+    fn ensure_in_sync(&self, deliverable: &Deliverable, status: &StatusFile) -> Result<(), AppError> {
+        if status.state != deliverable.state {
+            return Err(DomainError::InvalidState {
+                message: format!(
+                    "{} is {} on disk but {} in memory; reload before transitioning",
+                    deliverable.id, status.state, deliverable.state
+                ),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Add a "Needs Revisit" note to `_STATUS.md` without changing state.
+    ///
+    /// Returns `false` if the same note was already pending.
+    pub async fn flag_revisit(
+        &self,
+        deliverable: &Deliverable,
+        note: RevisitNote,
+    ) -> Result<bool, AppError> {
+        let mut status = self.load_status(deliverable).await?;
+        if !status.flag_revisit(note) {
+            return Ok(false);
+        }
+        self.write_status(deliverable, &status).await?;
+        Ok(true)
+    }
+
+    async fn write_status(
+        &self,
+        deliverable: &Deliverable,
+        status: &StatusFile,
+    ) -> Result<(), AppError> {
+        let path = deliverable.folder_path.join(DocumentType::Status.filename());
+        self.workspace
+            .write(&path, status.render().as_bytes())
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use chirality_domain::{ContentHash, DocumentState, PackageId, StateTransition};
+    use std::path::PathBuf;
+
+    fn deliverable() -> Deliverable {
+        Deliverable::new(
+            PackageId::from_legacy(1),
+            "Foundation",
+            PathBuf::from("/proj/PKG-001/DEL-01.01"),
+        )
+        .with_legacy_id(1, 1)
+    }
+
+    #[tokio::test]
+    async fn transition_preserves_human_notes() {
+        let workspace = Arc::new(InMemoryWorkspace::new().with_file(
+            "/proj/PKG-001/DEL-01.01/_STATUS.md",
+            "# Status\n\n<!-- chirality:status:begin -->\n- **State:** OPEN\n<!-- chirality:status:end -->\n\n## Notes\nKeep me.\n",
+        ));
+        let service = DeliverableService::new(workspace.clone());
+        let mut deliverable = deliverable();
+
+        service
+            .transition(
+                &mut deliverable,
+                DeliverableState::Initialized,
+                ActorId::human("alice"),
+                Some("Kick-off".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(deliverable.state, DeliverableState::Initialized);
+        assert_eq!(deliverable.history().len(), 1);
+        let written = workspace
+            .contents("/proj/PKG-001/DEL-01.01/_STATUS.md")
+            .unwrap();
+        assert!(written.contains("- **State:** INITIALIZED\n"));
+        assert!(written.contains("- **Last Transition:** OPEN -> INITIALIZED\n"));
+        assert!(written.contains("| OPEN -> INITIALIZED | HUMAN:alice | Kick-off\n"));
+        assert!(written.ends_with("## Notes\nKeep me.\n"));
+    }
+
+    #[tokio::test]
+    async fn refuses_when_disk_state_differs() {
+        let workspace = Arc::new(InMemoryWorkspace::new().with_file(
+            "/proj/PKG-001/DEL-01.01/_STATUS.md",
+            "<!-- chirality:status:begin -->\n- **State:** CHECKING\n<!-- chirality:status:end -->\n",
+        ));
+        let service = DeliverableService::new(workspace);
+        let mut deliverable = deliverable();
+
+        let result = service
+            .transition(
+                &mut deliverable,
+                DeliverableState::Initialized,
+                ActorId::human("alice"),
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Domain(DomainError::InvalidState { .. }))
+        ));
+        assert_eq!(deliverable.state, DeliverableState::Open);
+    }
+
+    #[tokio::test]
+    async fn revise_persists_new_revision_and_frozen_issue() {
+        let workspace = Arc::new(InMemoryWorkspace::new().with_file(
+            "/proj/PKG-001/DEL-01.01/_STATUS.md",
+            "<!-- chirality:status:begin -->\n- **State:** ISSUED\n- **Revision:** A\n<!-- chirality:status:end -->\n",
+        ));
+        let service = DeliverableService::new(workspace.clone());
+        let mut deliverable = deliverable().with_history(vec![StateTransition {
+            from: DeliverableState::Checking,
+            to: DeliverableState::Issued,
+            actor: ActorId::human("bob"),
+            at: chrono::Utc::now(),
+            reason: None,
+        }]);
+        deliverable.state = DeliverableState::Issued;
+        let mut documents: Vec<Document> = DocumentType::ALL
+            .into_iter()
+            .filter(DocumentType::is_core)
+            .map(|t| {
+                let mut doc = Document::new(
+                    deliverable.id.clone(),
+                    t,
+                    deliverable.folder_path.join(t.filename()),
+                    ContentHash::from_bytes(t.filename().as_bytes()),
+                    deliverable.revision.clone(),
+                    ActorId::human("alice"),
+                );
+                doc.state = DocumentState::Issued;
+                doc
+            })
+            .collect();
+
+        service
+            .revise(&mut deliverable, &mut documents, ActorId::human("alice"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(deliverable.revision.as_str(), "B");
+        assert!(documents.iter().all(|d| d.state == DocumentState::Draft));
+        let written = StatusFile::parse(
+            &workspace
+                .contents("/proj/PKG-001/DEL-01.01/_STATUS.md")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(written.state, DeliverableState::InProgress);
+        assert_eq!(written.revision.as_ref().map(|r| r.as_str()), Some("B"));
+        assert_eq!(written.issued_revisions.len(), 1);
+        assert_eq!(written.issued_revisions[0].revision().as_str(), "A");
+    }
+
+    #[tokio::test]
+    async fn submit_for_checking_reads_folder_and_reports_findings() {
+        let workspace = InMemoryWorkspace::new()
+            .with_file(
+                "/proj/PKG-001/DEL-01.01/_STATUS.md",
+                "<!-- chirality:status:begin -->\n- **State:** IN_PROGRESS\n<!-- chirality:status:end -->\n",
+            )
+            .with_file("/proj/PKG-001/DEL-01.01/Datasheet.md", "Load: TBD\n")
+            .with_file("/proj/PKG-001/DEL-01.01/Specification.md", "# Spec\n")
+            .with_file("/proj/PKG-001/DEL-01.01/Guidance.md", "# Guidance\n")
+            .with_file("/proj/PKG-001/DEL-01.01/Procedure.md", "# Procedure\n")
+            .with_file("/proj/PKG-001/DEL-01.01/calcs/loads.xlsx", "");
+        let workspace = Arc::new(workspace);
+        let service = DeliverableService::new(workspace.clone());
+        let mut deliverable = deliverable();
+        deliverable.state = DeliverableState::InProgress;
+        deliverable.anticipated_artifacts = vec!["loads.xlsx".to_string()];
+
+        let report = service
+            .readiness(&deliverable, &ReadinessChecks::standard())
+            .await
+            .unwrap();
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].check, "no-placeholders");
+        let result = service
+            .submit_for_checking(
+                &mut deliverable,
+                &ReadinessChecks::standard(),
+                ActorId::human("alice"),
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Domain(DomainError::PreconditionFailed { .. }))
+        ));
+
+        workspace
+            .write(
+                std::path::Path::new("/proj/PKG-001/DEL-01.01/Datasheet.md"),
+                b"Load: 40 kN\n",
+            )
+            .await
+            .unwrap();
+        service
+            .submit_for_checking(
+                &mut deliverable,
+                &ReadinessChecks::standard(),
+                ActorId::human("alice"),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(deliverable.state, DeliverableState::Checking);
+    }
+}
@@ -0,0 +1,74 @@
+//! Dependency scan - builds the project dependency graph from `_DEPENDENCIES.md` files.
+
+use std::sync::Arc;
+
+use chirality_domain::{Dependency, DependencyGraph, Deliverable, DocumentType};
+use chirality_ports::{PortError, WorkspacePort};
+
+use crate::error::AppError;
+
+/// Reads every deliverable's `_DEPENDENCIES.md` into a `DependencyGraph`.
+pub struct DependencyScanner<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+}
+
+impl<W: WorkspacePort + ?Sized> DependencyScanner<W> {
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self { workspace }
+    }
+
+    /// Build the graph for `deliverables` (usually a `WorkspaceSnapshot`'s).
+    ///
+    /// Deliverables without `_DEPENDENCIES.md` are added with no dependencies.
+    pub async fn scan(&self, deliverables: &[Deliverable]) -> Result<DependencyGraph, AppError> {
+        let mut graph = DependencyGraph::new();
+        for deliverable in deliverables {
+            let path = deliverable
+                .folder_path
+                .join(DocumentType::Dependencies.filename());
+            let dependencies = match self.workspace.read(&path).await {
+                Ok(bytes) => Dependency::parse_file(&deliverable.id, &String::from_utf8_lossy(&bytes)),
+                Err(PortError::FileNotFound { .. }) => Vec::new(),
+                Err(e) => return Err(e.into()),
+            };
+            graph.add(deliverable, dependencies);
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use crate::workspace_discovery::WorkspaceScanner;
+    use chirality_domain::DeliverableId;
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn builds_graph_from_snapshot() {
+        let workspace = Arc::new(
+            InMemoryWorkspace::new()
+                .with_file(
+                    "/proj/PKG-001_Civil/DEL-01.01_Foundation/_DEPENDENCIES.md",
+                    "- [ ] DEL-01.02 Geotech report\n",
+                )
+                .with_dir("/proj/PKG-001_Civil/DEL-01.02_Geotech"),
+        );
+        let snapshot = WorkspaceScanner::new(workspace.clone())
+            .scan(Path::new("/proj"))
+            .await
+            .unwrap();
+
+        let graph = DependencyScanner::new(workspace)
+            .scan(&snapshot.deliverables)
+            .await
+            .unwrap();
+        let foundation = DeliverableId::from_legacy(1, 1);
+        let geotech = DeliverableId::from_legacy(1, 2);
+        assert_eq!(graph.topological_order().unwrap(), [geotech.clone(), foundation.clone()]);
+        let blockers = graph.blocked_by(&foundation);
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers[0].upstream, geotech);
+    }
+}
@@ -0,0 +1,107 @@
+//! Document service - content updates that never silently overwrite edits.
+
+use std::sync::Arc;
+
+use chirality_domain::{ActorId, ContentHash, Document};
+use chirality_ports::WorkspacePort;
+
+use crate::error::AppError;
+
+/// Writes document content with compare-and-swap on the recorded hash.
+pub struct DocumentService<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+}
+
+impl<W: WorkspacePort + ?Sized> DocumentService<W> {
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self { workspace }
+    }
+
+    /// Write `content` to the document's file and record the new hash.
+    ///
+    /// The write only happens if the file still has the content the document
+    /// last recorded. If a human or another agent changed it in the meantime
+    /// this fails with `PortError::WriteConflict` carrying both hashes, and
+    /// neither the file nor the document is modified.
+    pub async fn update(
+        &self,
+        document: &mut Document,
+        content: &[u8],
+        actor: ActorId,
+    ) -> Result<ContentHash, AppError> {
+        let hash = self
+            .workspace
+            .write_if_unchanged(&document.file_path, content, Some(&document.content_hash))
+            .await?;
+        document.update_content(hash.clone(), actor);
+        Ok(hash)
+    }
+
+    /// Adopt the content currently on disk, e.g. after resolving a conflict.
+    ///
+    /// Returns whether the recorded hash changed.
+    pub async fn refresh(&self, document: &mut Document, actor: ActorId) -> Result<bool, AppError> {
+        let hash = self.workspace.hash(&document.file_path).await?;
+        if hash == document.content_hash {
+            return Ok(false);
+        }
+        document.update_content(hash, actor);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use chirality_domain::{DeliverableId, DocumentType, Revision};
+    use chirality_ports::PortError;
+    use std::path::{Path, PathBuf};
+
+    const PATH: &str = "/proj/PKG-001/DEL-01.01/Datasheet.md";
+
+    fn document() -> Document {
+        Document::new(
+            DeliverableId::from_legacy(1, 1),
+            DocumentType::Datasheet,
+            PathBuf::from(PATH),
+            ContentHash::from_bytes(b"v1"),
+            Revision::default(),
+            ActorId::agent("4_DOCUMENTS"),
+        )
+    }
+
+    #[tokio::test]
+    async fn concurrent_edit_becomes_a_visible_conflict() {
+        let workspace = Arc::new(InMemoryWorkspace::new().with_file(PATH, "v1"));
+        let service = DocumentService::new(workspace.clone());
+        let mut agent_view = document();
+        let mut human_view = document();
+
+        service
+            .update(&mut human_view, b"human edit", ActorId::human("alice"))
+            .await
+            .unwrap();
+        let result = service
+            .update(&mut agent_view, b"agent edit", ActorId::agent("4_DOCUMENTS"))
+            .await;
+
+        match result {
+            Err(AppError::Port(PortError::WriteConflict { expected, actual, .. })) => {
+                assert_eq!(expected, Some(ContentHash::from_bytes(b"v1")));
+                assert_eq!(actual, Some(ContentHash::from_bytes(b"human edit")));
+            }
+            other => panic!("expected a write conflict, got {:?}", other),
+        }
+        assert_eq!(workspace.contents(PATH).unwrap(), "human edit");
+        assert_eq!(agent_view.content_hash, ContentHash::from_bytes(b"v1"));
+
+        assert!(service.refresh(&mut agent_view, ActorId::agent("4_DOCUMENTS")).await.unwrap());
+        service
+            .update(&mut agent_view, b"agent edit", ActorId::agent("4_DOCUMENTS"))
+            .await
+            .unwrap();
+        assert_eq!(workspace.read(Path::new(PATH)).await.unwrap(), b"agent edit");
+        assert_eq!(agent_view.updated_by, ActorId::agent("4_DOCUMENTS"));
+    }
+}
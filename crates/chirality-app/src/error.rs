@@ -0,0 +1,16 @@
+//! Application error types.
+
+use thiserror::Error;
+
+use chirality_domain::DomainError;
+use chirality_ports::PortError;
+
+/// Errors from application services.
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error(transparent)]
+    Domain(#[from] DomainError),
+
+    #[error(transparent)]
+    Port(#[from] PortError),
+}
@@ -0,0 +1,314 @@
+//! Guarded workspace - enforces a session's write scope on every mutation.
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chirality_domain::{
+    AgentSession, ContentHash, Deliverable, DomainError, WriteAttempt, WriteGuard, WriteOperation,
+    WriteScope,
+};
+use chirality_ports::{FsChangeStream, PortError, StagedChange, WorkspacePort, WorkspaceTransaction};
+
+/// A `WorkspacePort` that checks `write`, `delete`, `create_dir_all`,
+/// `scaffold_deliverable` and every change in a committed transaction
+/// against a `WriteScope` before delegating.
+///
+/// Denied calls never reach the inner workspace and fail with
+/// `PortError::PermissionDenied`. Every check is recorded so it can be added
+/// to the session's audit trail. Reads pass straight through.
+///
+/// Scopes hold absolute paths, so a relative path is joined onto the root
+/// given with [`GuardedWorkspace::with_root`] (the inner workspace's root)
+/// before it is checked. Without a root, relative paths are denied.
+pub struct GuardedWorkspace<W: WorkspacePort + ?Sized> {
+    inner: Arc<W>,
+    scope: WriteScope,
+    root: Option<PathBuf>,
+    attempts: Mutex<Vec<WriteAttempt>>,
+}
+
+impl<W: WorkspacePort + ?Sized> GuardedWorkspace<W> {
+    pub fn new(inner: Arc<W>, scope: WriteScope) -> Self {
+        Self {
+            inner,
+            scope,
+            root: None,
+            attempts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Guard with the session's write scope.
+    pub fn for_session(inner: Arc<W>, session: &AgentSession) -> Self {
+        Self::new(inner, session.write_scope.clone())
+    }
+
+    /// Resolve relative paths against `root`, which must be the root the
+    /// inner workspace resolves them against.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    pub fn scope(&self) -> &WriteScope {
+        &self.scope
+    }
+
+    /// Attempts recorded so far, oldest first.
+    pub fn attempts(&self) -> Vec<WriteAttempt> {
+        self.attempts.lock().unwrap().clone()
+    }
+
+    /// Remove and return the recorded attempts (e.g. to move them onto the
+    /// session with `AgentSession::record_write_attempts`).
+    pub fn take_attempts(&self) -> Vec<WriteAttempt> {
+        std::mem::take(&mut *self.attempts.lock().unwrap())
+    }
+
+    fn check(&self, operation: WriteOperation, path: &Path) -> Result<(), PortError> {
+        let target = match &self.root {
+            Some(root) => root.join(path),
+            None => path.to_path_buf(),
+        };
+        let denied = if target.is_relative() {
+            Some("relative path and no workspace root to resolve it against".to_string())
+        } else {
+            match WriteGuard::ensure_allowed(&self.scope, &target) {
+                Ok(()) => None,
+                Err(DomainError::WriteViolation { reason, .. }) => Some(reason),
+                Err(e) => Some(e.to_string()),
+            }
+        };
+        self.attempts.lock().unwrap().push(WriteAttempt {
+            at: chrono::Utc::now(),
+            operation,
+            path: target,
+            denied: denied.clone(),
+        });
+        match denied {
+            None => Ok(()),
+            Some(reason) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    operation = ?operation,
+                    scope = %self.scope.describe(),
+                    "write denied by scope"
+                );
+                Err(PortError::PermissionDenied {
+                    path: path.to_path_buf(),
+                    reason,
+                })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<W: WorkspacePort + ?Sized> WorkspacePort for GuardedWorkspace<W> {
+    async fn read(&self, path: &Path) -> Result<Vec<u8>, PortError> {
+        self.inner.read(path).await
+    }
+
+    async fn write(&self, path: &Path, content: &[u8]) -> Result<ContentHash, PortError> {
+        self.check(WriteOperation::Write, path)?;
+        self.inner.write(path, content).await
+    }
+
+    async fn write_if_unchanged(
+        &self,
+        path: &Path,
+        content: &[u8],
+        expected: Option<&ContentHash>,
+    ) -> Result<ContentHash, PortError> {
+        self.check(WriteOperation::Write, path)?;
+        self.inner.write_if_unchanged(path, content, expected).await
+    }
+
+    async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>, PortError> {
+        self.inner.list_dir(path).await
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, PortError> {
+        self.inner.exists(path).await
+    }
+
+    async fn is_dir(&self, path: &Path) -> Result<bool, PortError> {
+        self.inner.is_dir(path).await
+    }
+
+    async fn hash(&self, path: &Path) -> Result<ContentHash, PortError> {
+        self.inner.hash(path).await
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> Result<(), PortError> {
+        self.check(WriteOperation::CreateDir, path)?;
+        self.inner.create_dir_all(path).await
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), PortError> {
+        self.check(WriteOperation::Delete, path)?;
+        self.inner.delete(path).await
+    }
+
+    async fn scaffold_deliverable(&self, deliverable: &Deliverable) -> Result<(), PortError> {
+        self.check(WriteOperation::Scaffold, &deliverable.folder_path)?;
+        self.inner.scaffold_deliverable(deliverable).await
+    }
+
+    async fn watch(&self, path: &Path) -> Result<FsChangeStream, PortError> {
+        self.inner.watch(path).await
+    }
+
+    /// A single out-of-scope change rejects the whole transaction.
+    async fn commit_transaction(
+        &self,
+        transaction: WorkspaceTransaction,
+    ) -> Result<Vec<(PathBuf, ContentHash)>, PortError> {
+        let mut denied = None;
+        for change in transaction.changes() {
+            let operation = match change {
+                StagedChange::Write { .. } => WriteOperation::Write,
+                StagedChange::Delete { .. } => WriteOperation::Delete,
+            };
+            if let Err(e) = self.check(operation, change.path()) {
+                denied.get_or_insert(e);
+            }
+        }
+        match denied {
+            Some(e) => Err(e),
+            None => self.inner.commit_transaction(transaction).await,
+        }
+    }
+
+    async fn recover_transactions(&self) -> Result<Vec<String>, PortError> {
+        self.inner.recover_transactions().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use chirality_domain::{ActorId, DeliverableId, PackageId, SessionBrief, SessionScope};
+
+    const FOLDER: &str = "/proj/PKG-001_Civil/DEL-01.01_Foundation";
+
+    fn session() -> AgentSession {
+        let id = DeliverableId::from_legacy(1, 1);
+        AgentSession::new_task(
+            "4_DOCUMENTS",
+            SessionBrief {
+                task_definition: "Generate docs".to_string(),
+                scope_description: String::new(),
+                output_contract: vec![],
+                constraints: vec![],
+                success_criteria: vec![],
+                inputs: serde_json::Value::Null,
+            },
+            SessionScope::Deliverable {
+                deliverable_id: id.clone(),
+            },
+            WriteScope::DeliverableLocal {
+                deliverable_id: id,
+                deliverable_path: PathBuf::from(FOLDER),
+            },
+            ActorId::agent("4_DOCUMENTS"),
+        )
+    }
+
+    #[tokio::test]
+    async fn denies_writes_outside_scope_and_records_every_attempt() {
+        let inner = Arc::new(InMemoryWorkspace::new().with_file("/proj/_COORDINATION.md", "# Coordination"));
+        let mut session = session();
+        let guarded = GuardedWorkspace::for_session(inner.clone(), &session);
+
+        guarded
+            .write(&Path::new(FOLDER).join("Datasheet.md"), b"# Datasheet")
+            .await
+            .unwrap();
+        let denied = guarded.write(Path::new("/proj/_COORDINATION.md"), b"overwritten").await;
+        match denied {
+            Err(PortError::PermissionDenied { path, reason }) => {
+                assert_eq!(path, PathBuf::from("/proj/_COORDINATION.md"));
+                assert!(reason.contains("outside deliverable folder"), "{}", reason);
+            }
+            other => panic!("expected PermissionDenied, got {:?}", other),
+        }
+        assert!(guarded.delete(Path::new("/proj/_COORDINATION.md")).await.is_err());
+        let other = Deliverable::new(
+            PackageId::from_legacy(1),
+            "Grading",
+            PathBuf::from("/proj/PKG-001_Civil/DEL-01.02_Grading"),
+        );
+        assert!(guarded.scaffold_deliverable(&other).await.is_err());
+
+        assert_eq!(inner.contents("/proj/_COORDINATION.md").unwrap(), "# Coordination");
+        assert!(inner.contents("/proj/PKG-001_Civil/DEL-01.02_Grading/_STATUS.md").is_none());
+        assert_eq!(guarded.read(Path::new("/proj/_COORDINATION.md")).await.unwrap(), b"# Coordination");
+
+        session.record_write_attempts(guarded.take_attempts());
+        let trail: Vec<_> = session
+            .write_attempts
+            .iter()
+            .map(|a| (a.operation, a.is_allowed()))
+            .collect();
+        assert_eq!(
+            trail,
+            [
+                (WriteOperation::Write, true),
+                (WriteOperation::Write, false),
+                (WriteOperation::Delete, false),
+                (WriteOperation::Scaffold, false),
+            ]
+        );
+        assert!(guarded.attempts().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_transactions_with_any_out_of_scope_change() {
+        let inner = Arc::new(InMemoryWorkspace::new());
+        let guarded = GuardedWorkspace::for_session(inner.clone(), &session());
+        let mut transaction = WorkspaceTransaction::begin();
+        transaction
+            .write(Path::new(FOLDER).join("Datasheet.md"), "# Datasheet")
+            .write("/proj/_COORDINATION.md", "# Coordination");
+
+        assert!(matches!(
+            guarded.commit_transaction(transaction).await,
+            Err(PortError::PermissionDenied { .. })
+        ));
+        assert!(inner.contents(format!("{}/Datasheet.md", FOLDER)).is_none());
+        assert_eq!(guarded.attempts().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn relative_paths_are_checked_against_the_workspace_root() {
+        let inner = Arc::new(InMemoryWorkspace::new());
+        let scope = WriteScope::Composite {
+            allow: vec![WriteScope::ToolRootOnly {
+                root_path: PathBuf::from("/proj"),
+            }],
+            deny: vec![WriteScope::ToolRootOnly {
+                root_path: PathBuf::from("/proj/Commercial"),
+            }],
+        };
+
+        let unrooted = GuardedWorkspace::new(inner.clone(), scope.clone());
+        assert!(matches!(
+            unrooted.write(Path::new("Notes.md"), b"x").await,
+            Err(PortError::PermissionDenied { .. })
+        ));
+
+        let guarded = GuardedWorkspace::new(inner.clone(), scope).with_root("/proj");
+        assert!(matches!(
+            guarded.write(Path::new("Commercial/rates.md"), b"x").await,
+            Err(PortError::PermissionDenied { .. })
+        ));
+        assert!(matches!(
+            guarded.write(Path::new("Civil/../Commercial/rates.md"), b"x").await,
+            Err(PortError::PermissionDenied { .. })
+        ));
+        guarded.write(Path::new("Civil/notes.md"), b"ok").await.unwrap();
+        assert_eq!(guarded.attempts()[2].path, PathBuf::from("/proj/Civil/notes.md"));
+    }
+}
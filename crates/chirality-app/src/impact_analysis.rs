@@ -0,0 +1,274 @@
+//! Impact analysis - which deliverables a change to upstream documents affects.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::Utc;
+use chirality_domain::{
+    CommitHash, Deliverable, DeliverableId, DependencyGraph, DependencyKind, DocumentType,
+    RevisitNote,
+};
+use chirality_ports::{GitPort, WorkspacePort};
+
+use crate::deliverable_service::DeliverableService;
+use crate::error::AppError;
+
+/// A deliverable downstream of a change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedDeliverable {
+    pub deliverable_id: DeliverableId,
+    /// Changed deliverable the impact comes from.
+    pub origin: DeliverableId,
+    pub kind: DependencyKind,
+    /// e.g. `depends on DEL-01.02, which changed (Datasheet.md)`.
+    pub reason: String,
+}
+
+/// Outcome of an impact analysis.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImpactReport {
+    /// Changed files grouped by the deliverable folder they are in.
+    pub changed: BTreeMap<DeliverableId, Vec<PathBuf>>,
+    pub affected: Vec<AffectedDeliverable>,
+}
+
+/// Walks the dependency graph from changed deliverables to their dependents.
+pub struct ImpactAnalyzer<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+}
+
+impl<W: WorkspacePort + ?Sized> ImpactAnalyzer<W> {
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self { workspace }
+    }
+
+    /// Analyse a set of changed paths.
+    ///
+    /// Changes to `_STATUS.md` are ignored: they record lifecycle progress
+    /// (including revisit flags), not content a dependent relies on.
+    pub fn analyze_paths(
+        &self,
+        deliverables: &[Deliverable],
+        graph: &DependencyGraph,
+        changed_paths: &[PathBuf],
+    ) -> ImpactReport {
+        let mut report = ImpactReport::default();
+        for path in changed_paths {
+            if path.file_name() == Some(DocumentType::Status.filename().as_ref()) {
+                continue;
+            }
+            let owner = deliverables
+                .iter()
+                .filter(|d| path.starts_with(&d.folder_path))
+                .max_by_key(|d| d.folder_path.components().count());
+            if let Some(owner) = owner {
+                let relative = path.strip_prefix(&owner.folder_path).unwrap_or(path);
+                report
+                    .changed
+                    .entry(owner.id.clone())
+                    .or_default()
+                    .push(relative.to_path_buf());
+            }
+        }
+
+        let changed: Vec<DeliverableId> = report.changed.keys().cloned().collect();
+        for impact in graph.downstream_of(&changed) {
+            let files: Vec<String> = report.changed[impact.origin()]
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            report.affected.push(AffectedDeliverable {
+                reason: format!("{} ({})", impact.reason(), files.join(", ")),
+                origin: impact.origin().clone(),
+                kind: impact.kind,
+                deliverable_id: impact.deliverable,
+            });
+        }
+        report
+    }
+
+    /// Analyse the paths changed between two commits.
+    ///
+    /// Git reports paths relative to the repository root, which is taken to
+    /// be `workspace_root`.
+    pub async fn analyze_commits<G: GitPort + ?Sized>(
+        &self,
+        git: &G,
+        from: &CommitHash,
+        to: &CommitHash,
+        workspace_root: &Path,
+        deliverables: &[Deliverable],
+        graph: &DependencyGraph,
+    ) -> Result<ImpactReport, AppError> {
+        let paths: Vec<PathBuf> = git
+            .changed_paths(from, to)
+            .await?
+            .into_iter()
+            .map(|p| workspace_root.join(p))
+            .collect();
+        Ok(self.analyze_paths(deliverables, graph, &paths))
+    }
+
+    /// Add a "Needs Revisit" note to each affected deliverable's `_STATUS.md`.
+    ///
+    /// Returns the ids of the deliverables that gained a note; ones already
+    /// flagged for the same reason are left alone.
+    pub async fn flag_revisit(
+        &self,
+        deliverables: &[Deliverable],
+        report: &ImpactReport,
+    ) -> Result<Vec<DeliverableId>, AppError> {
+        let service = DeliverableService::new(self.workspace.clone());
+        let at = Utc::now();
+        let mut flagged = Vec::new();
+        for affected in &report.affected {
+            let Some(deliverable) = deliverables.iter().find(|d| d.id == affected.deliverable_id)
+            else {
+                continue;
+            };
+            let note = RevisitNote {
+                at,
+                upstream: affected.origin.clone(),
+                reason: affected.reason.clone(),
+            };
+            if service.flag_revisit(deliverable, note).await? {
+                flagged.push(deliverable.id.clone());
+            }
+        }
+        Ok(flagged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependency_scan::DependencyScanner;
+    use crate::test_support::{InMemoryWorkspace, RecordingGit};
+    use crate::workspace_discovery::WorkspaceScanner;
+
+    const STATUS: &str =
+        "<!-- chirality:status:begin -->\n- **State:** IN_PROGRESS\n<!-- chirality:status:end -->\n";
+
+    fn workspace() -> Arc<InMemoryWorkspace> {
+        Arc::new(
+            InMemoryWorkspace::new()
+                .with_file("/proj/PKG-001_Civil/DEL-01.01_Geotech/Datasheet.md", "# DS")
+                .with_file(
+                    "/proj/PKG-001_Civil/DEL-01.02_Foundation/_DEPENDENCIES.md",
+                    "- DEL-01.01 Soil data\n",
+                )
+                .with_file("/proj/PKG-001_Civil/DEL-01.02_Foundation/_STATUS.md", STATUS)
+                .with_file(
+                    "/proj/PKG-002_Structure/DEL-02.01_Columns/_DEPENDENCIES.md",
+                    "- DEL-01.02 Footing loads\n",
+                ),
+        )
+    }
+
+    #[tokio::test]
+    async fn lists_and_flags_downstream_deliverables() {
+        let workspace = workspace();
+        let snapshot = WorkspaceScanner::new(workspace.clone())
+            .scan(Path::new("/proj"))
+            .await
+            .unwrap();
+        let graph = DependencyScanner::new(workspace.clone())
+            .scan(&snapshot.deliverables)
+            .await
+            .unwrap();
+        let analyzer = ImpactAnalyzer::new(workspace.clone());
+
+        let report = analyzer.analyze_paths(
+            &snapshot.deliverables,
+            &graph,
+            &[
+                PathBuf::from("/proj/PKG-001_Civil/DEL-01.01_Geotech/Datasheet.md"),
+                PathBuf::from("/proj/PKG-001_Civil/DEL-01.01_Geotech/_STATUS.md"),
+                PathBuf::from("/proj/README.md"),
+            ],
+        );
+        assert_eq!(report.changed.len(), 1);
+        let affected: Vec<(String, &str)> = report
+            .affected
+            .iter()
+            .map(|a| (a.deliverable_id.to_string(), a.reason.as_str()))
+            .collect();
+        assert_eq!(
+            affected,
+            [
+                (
+                    "DEL-01.02".to_string(),
+                    "depends on DEL-01.01, which changed (Datasheet.md)"
+                ),
+                (
+                    "DEL-02.01".to_string(),
+                    "depends on DEL-01.02 -> DEL-01.01, which changed (Datasheet.md)"
+                ),
+            ]
+        );
+
+        let flagged = analyzer.flag_revisit(&snapshot.deliverables, &report).await.unwrap();
+        assert_eq!(flagged.len(), 2);
+        let status = workspace
+            .contents("/proj/PKG-001_Civil/DEL-01.02_Foundation/_STATUS.md")
+            .unwrap();
+        assert!(status.contains("- **State:** IN_PROGRESS\n"));
+        assert!(status.contains("| DEL-01.01 | depends on DEL-01.01, which changed (Datasheet.md)\n"));
+        assert!(analyzer
+            .flag_revisit(&snapshot.deliverables, &report)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+    #[tokio::test]
+    async fn analyzes_paths_changed_between_commits() {
+        let workspace = workspace();
+        let snapshot = WorkspaceScanner::new(workspace.clone())
+            .scan(Path::new("/proj"))
+            .await
+            .unwrap();
+        let graph = DependencyScanner::new(workspace.clone())
+            .scan(&snapshot.deliverables)
+            .await
+            .unwrap();
+        let git = RecordingGit::new().with_changed_paths(
+            "abc",
+            "def",
+            ["PKG-001_Civil/DEL-01.02_Foundation/Guidance.md", "README.md"],
+        );
+
+        let report = ImpactAnalyzer::new(workspace)
+            .analyze_commits(
+                &git,
+                &CommitHash::from_string("abc"),
+                &CommitHash::from_string("def"),
+                Path::new("/proj"),
+                &snapshot.deliverables,
+                &graph,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.changed.len(), 1);
+        let affected: Vec<String> = report.affected.iter().map(|a| a.deliverable_id.to_string()).collect();
+        assert_eq!(affected, ["DEL-02.01"]);
+        assert_eq!(
+            report.affected[0].reason,
+            "depends on DEL-01.02, which changed (Guidance.md)"
+        );
+        assert!(matches!(
+            ImpactAnalyzer::new(Arc::new(InMemoryWorkspace::new()))
+                .analyze_commits(
+                    &git,
+                    &CommitHash::from_string("def"),
+                    &CommitHash::from_string("abc"),
+                    Path::new("/proj"),
+                    &[],
+                    &graph,
+                )
+                .await,
+            Err(AppError::Port(_))
+        ));
+    }
+}
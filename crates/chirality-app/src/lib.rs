@@ -8,10 +8,38 @@
 //! - **SessionOrchestrator**: Manages agent session lifecycle
 //! - **DeliverableService**: Manages deliverable lifecycle
 //! - **ProjectService**: Manages project operations
-//! - **DocumentService**: Manages document operations
+//! - **DocumentService**: Document content updates with conflict detection
+//! - **WorkspaceScanner**: Rebuilds entities from the workspace folder tree
+//! - **AgentRegistry**: Agent definitions loaded from AGENT_*.md files
+//! - **ScopedReader**: Read-scoped file access for agent sessions
+//! - **GuardedWorkspace**: Write-scope enforcement on every workspace mutation
+//! - **BriefResolver**: Binds brief inputs to workspace entities
+//! - **BriefGenerator**: Drafts TASK briefs from deliverable context
+//! - **OutputVerifier**: Checks TASK outputs against the brief's contract
+//! - **DependencyScanner**: Builds the deliverable dependency graph
+//! - **ImpactAnalyzer**: Finds deliverables downstream of a change
+//! - **DecompositionService**: Materialises the decomposition as folders
+//!   and blocks commits that break the package scope partition
+
+pub mod agent_registry;
+pub mod brief_generation;
+pub mod brief_resolution;
+pub mod decomposition_service;
+pub mod deliverable_service;
+pub mod dependency_scan;
+pub mod document_service;
+pub mod error;
+pub mod guarded_workspace;
+pub mod impact_analysis;
+pub mod read_access;
+pub mod task_verification;
+pub mod workspace_discovery;
+
+pub use error::AppError;
+
+#[cfg(test)]
+pub(crate) mod test_support;
 
 // Services will be implemented in Phase 5
 // pub mod session_orchestrator;
-// pub mod deliverable_service;
 // pub mod project_service;
-// pub mod document_service;
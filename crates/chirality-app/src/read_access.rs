@@ -0,0 +1,208 @@
+//! Read-scoped file access for agent sessions.
+//!
+//! Everything an agent sees goes through a `ScopedReader`: the
+//! `ExecutionContext` it assembles only lists context files the scope allows,
+//! and files requested by agent file-read tools are checked the same way.
+//! Relative paths are resolved against the workspace root, as the workspace
+//! resolves them. Denied paths are logged and kept for the session record.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chirality_domain::{
+    AgentSession, DomainError, ReadGuard, ReadScope, ReadValidation, ReadViolation, WriteScope,
+};
+use chirality_ports::{ExecutionContext, WorkspacePort};
+
+use crate::error::AppError;
+
+/// Workspace reads filtered through a session's read scope.
+pub struct ScopedReader<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+    root: PathBuf,
+    scope: ReadScope,
+    denials: Mutex<Vec<ReadViolation>>,
+}
+
+impl<W: WorkspacePort + ?Sized> ScopedReader<W> {
+    /// `root` must be the root the workspace resolves relative paths against.
+    pub fn new(workspace: Arc<W>, root: impl Into<PathBuf>, scope: ReadScope) -> Self {
+        Self {
+            workspace,
+            root: root.into(),
+            scope,
+            denials: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Reader using the session's declared read scope.
+    pub fn for_session(workspace: Arc<W>, root: impl Into<PathBuf>, session: &AgentSession) -> Self {
+        Self::new(workspace, root, session.read_scope.clone())
+    }
+
+    /// Build the executor context for `session`, keeping only the candidate
+    /// context files its read scope allows.
+    pub fn execution_context(
+        &self,
+        session: &AgentSession,
+        agent_instructions: impl Into<String>,
+        candidates: impl IntoIterator<Item = PathBuf>,
+    ) -> ExecutionContext {
+        let deliverable_path = match &session.write_scope {
+            WriteScope::DeliverableLocal {
+                deliverable_path, ..
+            } => Some(deliverable_path.clone()),
+            _ => None,
+        };
+        ExecutionContext {
+            workspace_path: self.root.clone(),
+            agent_instructions: agent_instructions.into(),
+            write_scope: session.write_scope.clone(),
+            read_scope: self.scope.clone(),
+            deliverable_path,
+            context_files: self.context_files(candidates),
+        }
+    }
+
+    /// Keep the candidate context files the scope allows, dropping the rest.
+    ///
+    /// Kept paths are returned resolved against the workspace root.
+    pub fn context_files(&self, candidates: impl IntoIterator<Item = PathBuf>) -> Vec<PathBuf> {
+        candidates
+            .into_iter()
+            .map(|path| self.root.join(path))
+            .filter(|path| self.check(path).is_ok())
+            .collect()
+    }
+
+    /// Serve an agent's request to read a file.
+    pub async fn read(&self, path: &Path) -> Result<Vec<u8>, AppError> {
+        let path = self.root.join(path);
+        if let Err(violation) = self.check(&path) {
+            return Err(DomainError::ReadViolation {
+                target_path: violation.target_path,
+                scope: violation.scope,
+                reason: violation.reason,
+            }
+            .into());
+        }
+        Ok(self.workspace.read(&path).await?)
+    }
+
+    /// Reads denied so far, oldest first.
+    pub fn denials(&self) -> Vec<ReadViolation> {
+        self.denials.lock().unwrap().clone()
+    }
+
+    fn check(&self, path: &Path) -> Result<(), ReadViolation> {
+        match ReadGuard::validate_read(&self.scope, path) {
+            ReadValidation::Allowed => Ok(()),
+            ReadValidation::Denied(violation) => {
+                tracing::warn!(
+                    target_path = %violation.target_path.display(),
+                    scope = %violation.scope,
+                    reason = %violation.reason,
+                    "read violation"
+                );
+                self.denials.lock().unwrap().push(violation.clone());
+                Err(violation)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use chirality_domain::{ActorId, DeliverableId, SessionBrief, SessionScope};
+
+    fn reader() -> ScopedReader<InMemoryWorkspace> {
+        let workspace = InMemoryWorkspace::new()
+            .with_file("/proj/PKG-001/DEL-01.01/Datasheet.md", "# DS")
+            .with_file("/proj/Commercial/rates.md", "secret");
+        let scope = ReadScope::Composite {
+            allow: vec![ReadScope::Within {
+                root_path: PathBuf::from("/proj"),
+            }],
+            deny: vec![ReadScope::Within {
+                root_path: PathBuf::from("/proj/Commercial"),
+            }],
+        };
+        ScopedReader::new(Arc::new(workspace), "/proj", scope)
+    }
+
+    #[test]
+    fn context_files_drop_denied_paths() {
+        let reader = reader();
+        let files = reader.context_files([
+            PathBuf::from("/proj/PKG-001/DEL-01.01/Datasheet.md"),
+            PathBuf::from("/proj/Commercial/rates.md"),
+        ]);
+        assert_eq!(files, vec![PathBuf::from("/proj/PKG-001/DEL-01.01/Datasheet.md")]);
+        assert_eq!(reader.denials().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn tool_reads_are_checked() {
+        let reader = reader();
+        assert_eq!(
+            reader
+                .read(Path::new("/proj/PKG-001/DEL-01.01/Datasheet.md"))
+                .await
+                .unwrap(),
+            b"# DS"
+        );
+        assert!(matches!(
+            reader.read(Path::new("/proj/Commercial/rates.md")).await,
+            Err(AppError::Domain(DomainError::ReadViolation { .. }))
+        ));
+        assert_eq!(
+            reader.denials()[0].target_path,
+            PathBuf::from("/proj/Commercial/rates.md")
+        );
+        assert!(matches!(
+            reader.read(Path::new("Commercial/rates.md")).await,
+            Err(AppError::Domain(DomainError::ReadViolation { .. }))
+        ));
+        assert_eq!(reader.read(Path::new("PKG-001/DEL-01.01/Datasheet.md")).await.unwrap(), b"# DS");
+    }
+
+    #[test]
+    fn execution_context_only_lists_readable_files() {
+        let reader = reader();
+        let id = DeliverableId::from_legacy(1, 1);
+        let session = AgentSession::new_task(
+            "4_DOCUMENTS",
+            SessionBrief {
+                task_definition: "Generate docs".to_string(),
+                scope_description: String::new(),
+                output_contract: vec![],
+                constraints: vec![],
+                success_criteria: vec![],
+                inputs: serde_json::Value::Null,
+            },
+            SessionScope::Deliverable {
+                deliverable_id: id.clone(),
+            },
+            WriteScope::DeliverableLocal {
+                deliverable_id: id,
+                deliverable_path: PathBuf::from("/proj/PKG-001/DEL-01.01"),
+            },
+            ActorId::human("alice"),
+        );
+
+        let context = reader.execution_context(
+            &session,
+            "# AGENT_4_DOCUMENTS",
+            [
+                PathBuf::from("PKG-001/DEL-01.01/Datasheet.md"),
+                PathBuf::from("Commercial/rates.md"),
+            ],
+        );
+        assert_eq!(context.workspace_path, PathBuf::from("/proj"));
+        assert_eq!(context.deliverable_path, Some(PathBuf::from("/proj/PKG-001/DEL-01.01")));
+        assert_eq!(context.context_files, [PathBuf::from("/proj/PKG-001/DEL-01.01/Datasheet.md")]);
+        assert_eq!(reader.denials()[0].target_path, PathBuf::from("/proj/Commercial/rates.md"));
+    }
+}
@@ -0,0 +1,296 @@
+//! Task verification - holds TASK sessions to their brief's output contract.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chirality_domain::{
+    contract_path, normalize_path, resolve_under, AgentSession, ContentHash, SuccessCriterion,
+    VerificationFinding, VerificationReport, WriteGuard, WriteScope, WriteValidation,
+};
+use chirality_ports::{PortError, TaskResult, WorkspacePort};
+
+use crate::error::AppError;
+
+/// Checks a finished TASK session's outputs against the workspace.
+pub struct OutputVerifier<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+}
+
+impl<W: WorkspacePort + ?Sized> OutputVerifier<W> {
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self { workspace }
+    }
+
+    /// Verify `result` against the session's brief and write scope.
+    ///
+    /// Relative paths resolve against the deliverable folder for
+    /// deliverable-local sessions and against `workspace_root` otherwise, and
+    /// every path is normalized before outputs are matched to the contract.
+    /// Files named by success criteria are only read if they resolve inside
+    /// that base folder.
+    pub async fn verify(
+        &self,
+        session: &AgentSession,
+        result: &TaskResult,
+        workspace_root: &Path,
+    ) -> Result<VerificationReport, AppError> {
+        let base = match &session.write_scope {
+            WriteScope::DeliverableLocal {
+                deliverable_path, ..
+            } => deliverable_path.as_path(),
+            _ => workspace_root,
+        };
+        let resolve = |path: &Path| normalize_path(&base.join(path));
+        let mut report = VerificationReport::default();
+
+        if !result.success {
+            report.findings.push(VerificationFinding::new(
+                "executor",
+                result
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "executor reported failure".to_string()),
+            ));
+        }
+
+        let reported: Vec<PathBuf> = result.outputs.iter().map(|o| resolve(&o.path)).collect();
+        for (output, path) in result.outputs.iter().zip(&reported) {
+            if let Some(finding) = scope_finding(&session.write_scope, path) {
+                report.findings.push(finding);
+                continue;
+            }
+            match self.hash(path).await? {
+                None => report.findings.push(VerificationFinding::new(
+                    "output-hash",
+                    format!("{} was reported but does not exist", path.display()),
+                )),
+                Some(hash) if hash != output.content_hash => {
+                    report.findings.push(VerificationFinding::new(
+                        "output-hash",
+                        format!(
+                            "{} on disk ({}) does not match reported hash {}",
+                            path.display(),
+                            hash,
+                            output.content_hash
+                        ),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+
+        let brief = session.brief.as_ref();
+        let contract: Vec<PathBuf> = brief
+            .map(|b| b.output_contract.iter().filter_map(|e| contract_path(e)).collect())
+            .unwrap_or_default();
+        for entry in &contract {
+            let path = resolve(entry);
+            if let Some(finding) = scope_finding(&session.write_scope, &path) {
+                report.findings.push(finding);
+            } else if !reported.contains(&path) {
+                report.findings.push(VerificationFinding::new(
+                    "output-contract",
+                    format!("{} was not produced", entry.display()),
+                ));
+            }
+        }
+
+        for criterion in brief.iter().flat_map(|b| &b.success_criteria) {
+            let Some(check) = SuccessCriterion::parse(criterion) else {
+                report.unchecked_criteria.push(criterion.clone());
+                continue;
+            };
+            for path in check.paths(&contract) {
+                let Some(resolved) = resolve_under(base, path) else {
+                    report.findings.push(VerificationFinding::new(
+                        "success-criteria",
+                        format!("{:?}: {} is outside {}", criterion, path.display(), base.display()),
+                    ));
+                    continue;
+                };
+                let content = self.read_optional(&resolved).await?;
+                if let Some(message) = check.check(path, content.as_deref()) {
+                    report.findings.push(VerificationFinding::new(
+                        "success-criteria",
+                        format!("{:?}: {}", criterion, message),
+                    ));
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Verify, record outputs, and complete or fail the session accordingly.
+    pub async fn finish(
+        &self,
+        session: &mut AgentSession,
+        result: TaskResult,
+        workspace_root: &Path,
+    ) -> Result<VerificationReport, AppError> {
+        let report = self.verify(session, &result, workspace_root).await?;
+        for output in result.outputs {
+            session.add_output(output);
+        }
+        if report.is_satisfied() {
+            session.complete();
+        } else {
+            tracing::warn!(
+                session = %session.id,
+                findings = report.findings.len(),
+                "task session failed output verification"
+            );
+            session.fail_with(report.summary());
+        }
+        Ok(report)
+    }
+
+    async fn hash(&self, path: &Path) -> Result<Option<ContentHash>, AppError> {
+        match self.workspace.hash(path).await {
+            Ok(hash) => Ok(Some(hash)),
+            Err(PortError::FileNotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn read_optional(&self, path: &Path) -> Result<Option<String>, AppError> {
+        match self.workspace.read(path).await {
+            Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+            Err(PortError::FileNotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+fn scope_finding(scope: &WriteScope, path: &Path) -> Option<VerificationFinding> {
+    match WriteGuard::validate_write(scope, path) {
+        WriteValidation::Allowed => None,
+        WriteValidation::Denied(violation) => Some(VerificationFinding::new(
+            "write-scope",
+            format!("{} is outside the write scope: {}", path.display(), violation.reason),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use chirality_domain::{
+        ActorId, DeliverableId, OutputType, SessionBrief, SessionOutput, SessionScope,
+        SessionState,
+    };
+
+    const FOLDER: &str = "/proj/PKG-001_Civil/DEL-01.01_Foundation";
+
+    fn session(criteria: &[&str]) -> AgentSession {
+        let id = DeliverableId::from_legacy(1, 1);
+        AgentSession::new_task(
+            "4_DOCUMENTS",
+            SessionBrief {
+                task_definition: "Generate docs".to_string(),
+                scope_description: String::new(),
+                output_contract: vec!["Datasheet.md".into(), "`Guidance.md`".into()],
+                constraints: vec![],
+                success_criteria: criteria.iter().map(|c| c.to_string()).collect(),
+                inputs: serde_json::Value::Null,
+            },
+            SessionScope::Deliverable {
+                deliverable_id: id.clone(),
+            },
+            WriteScope::DeliverableLocal {
+                deliverable_id: id,
+                deliverable_path: PathBuf::from(FOLDER),
+            },
+            ActorId::human("alice"),
+        )
+    }
+
+    fn output(path: &str, content: &str) -> SessionOutput {
+        SessionOutput {
+            output_type: OutputType::Document,
+            path: PathBuf::from(path),
+            content_hash: ContentHash::from_bytes(content.as_bytes()),
+            description: None,
+        }
+    }
+
+    fn result(outputs: Vec<SessionOutput>) -> TaskResult {
+        TaskResult {
+            success: true,
+            outputs,
+            log: String::new(),
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn completes_when_contract_is_met() {
+        let workspace = InMemoryWorkspace::new()
+            .with_file(format!("{}/Datasheet.md", FOLDER), "# Datasheet\n## Loads\n")
+            .with_file(format!("{}/Guidance.md", FOLDER), "# Guidance\n");
+        let verifier = OutputVerifier::new(Arc::new(workspace));
+        let mut session =
+            session(&["section \"Loads\" in Datasheet.md", "no placeholders", "Reads well"]);
+        let result = result(vec![
+            output("Datasheet.md", "# Datasheet\n## Loads\n"),
+            output(&format!("{}/Guidance.md", FOLDER), "# Guidance\n"),
+        ]);
+
+        let report = verifier.finish(&mut session, result, Path::new("/proj")).await.unwrap();
+        assert!(report.is_satisfied(), "{}", report.summary());
+        assert_eq!(report.unchecked_criteria, ["Reads well"]);
+        assert_eq!(session.state, SessionState::Completed);
+        assert_eq!(session.outputs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn fails_session_with_detailed_report() {
+        let workspace = InMemoryWorkspace::new()
+            .with_file(format!("{}/Datasheet.md", FOLDER), "# Datasheet\nLoad: TBD\n")
+            .with_file("/proj/PKG-001_Civil/notes.md", "stray");
+        let verifier = OutputVerifier::new(Arc::new(workspace));
+        let mut session = session(&["no placeholders: Datasheet.md", "exists: calcs.xlsx"]);
+        let result = result(vec![
+            output("Datasheet.md", "# Datasheet\n"),
+            output("../notes.md", "stray"),
+        ]);
+
+        let report = verifier.finish(&mut session, result, Path::new("/proj")).await.unwrap();
+        let checks: Vec<_> = report.findings.iter().map(|f| f.check).collect();
+        assert_eq!(
+            checks,
+            [
+                "output-hash",
+                "write-scope",
+                "output-contract",
+                "success-criteria",
+                "success-criteria"
+            ]
+        );
+        assert_eq!(session.state, SessionState::Failed);
+        assert!(session
+            .failure_reason
+            .as_deref()
+            .unwrap()
+            .contains("Guidance.md was not produced"));
+    }
+
+    #[tokio::test]
+    async fn matches_normalized_paths_and_refuses_criteria_outside_the_folder() {
+        let workspace = InMemoryWorkspace::new()
+            .with_file(format!("{}/Datasheet.md", FOLDER), "# Datasheet\n")
+            .with_file(format!("{}/Guidance.md", FOLDER), "# Guidance\n")
+            .with_file("/proj/secrets.md", "# Secrets\n");
+        let verifier = OutputVerifier::new(Arc::new(workspace));
+        let session = session(&["exists: ./calcs/../Datasheet.md", "exists: ../../secrets.md"]);
+        let result = result(vec![
+            output("./Datasheet.md", "# Datasheet\n"),
+            output(&format!("{}/drafts/../Guidance.md", FOLDER), "# Guidance\n"),
+        ]);
+
+        let report = verifier.verify(&session, &result, Path::new("/proj")).await.unwrap();
+        assert_eq!(report.findings.len(), 1, "{}", report.summary());
+        assert_eq!(report.findings[0].check, "success-criteria");
+        assert!(report.findings[0].message.contains("../../secrets.md is outside"));
+    }
+}
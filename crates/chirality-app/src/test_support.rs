@@ -0,0 +1,306 @@
+//! In-memory workspace and git fakes used by service tests.
+
+use async_trait::async_trait;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chirality_domain::{ActorId, CommitHash, ContentHash, Deliverable, DocumentType};
+use chirality_ports::{
+    CommitInfo, FsChangeStream, GitPort, PortError, StagedChange, WorkspacePort,
+    WorkspaceTransaction,
+};
+
+/// A `WorkspacePort` backed by a map of paths to file contents.
+#[derive(Default)]
+pub struct InMemoryWorkspace {
+    files: Mutex<BTreeMap<PathBuf, Vec<u8>>>,
+    dirs: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl InMemoryWorkspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file (and its parent directories).
+    pub fn with_file(self, path: impl AsRef<Path>, content: &str) -> Self {
+        self.put(path.as_ref(), content.as_bytes());
+        self
+    }
+
+    /// Add an empty directory (and its parents).
+    pub fn with_dir(self, path: impl AsRef<Path>) -> Self {
+        self.add_dirs(path.as_ref());
+        self
+    }
+
+    pub fn contents(&self, path: impl AsRef<Path>) -> Option<String> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(path.as_ref())
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn put(&self, path: &Path, content: &[u8]) {
+        if let Some(parent) = path.parent() {
+            self.add_dirs(parent);
+        }
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), content.to_vec());
+    }
+
+    fn add_dirs(&self, path: &Path) {
+        let mut dirs = self.dirs.lock().unwrap();
+        for ancestor in path.ancestors() {
+            if ancestor.as_os_str().is_empty() {
+                break;
+            }
+            dirs.insert(ancestor.to_path_buf());
+        }
+    }
+}
+
+#[async_trait]
+impl WorkspacePort for InMemoryWorkspace {
+    async fn read(&self, path: &Path) -> Result<Vec<u8>, PortError> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| PortError::FileNotFound {
+                path: path.to_path_buf(),
+            })
+    }
+
+    async fn write(&self, path: &Path, content: &[u8]) -> Result<ContentHash, PortError> {
+        self.put(path, content);
+        Ok(ContentHash::from_bytes(content))
+    }
+
+    async fn write_if_unchanged(
+        &self,
+        path: &Path,
+        content: &[u8],
+        expected: Option<&ContentHash>,
+    ) -> Result<ContentHash, PortError> {
+        let actual = self
+            .files
+            .lock()
+            .unwrap()
+            .get(path)
+            .map(|bytes| ContentHash::from_bytes(bytes));
+        if actual.as_ref() != expected {
+            return Err(PortError::WriteConflict {
+                path: path.to_path_buf(),
+                expected: expected.cloned(),
+                actual,
+            });
+        }
+        self.write(path, content).await
+    }
+
+    async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>, PortError> {
+        if !self.dirs.lock().unwrap().contains(path) {
+            return Err(PortError::FileNotFound {
+                path: path.to_path_buf(),
+            });
+        }
+        let mut entries: BTreeSet<PathBuf> = BTreeSet::new();
+        let files = self.files.lock().unwrap();
+        let dirs = self.dirs.lock().unwrap();
+        for candidate in files.keys().chain(dirs.iter()) {
+            if candidate.parent() == Some(path) {
+                entries.insert(candidate.clone());
+            }
+        }
+        Ok(entries.into_iter().collect())
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, PortError> {
+        Ok(self.files.lock().unwrap().contains_key(path)
+            || self.dirs.lock().unwrap().contains(path))
+    }
+
+    async fn is_dir(&self, path: &Path) -> Result<bool, PortError> {
+        Ok(self.dirs.lock().unwrap().contains(path))
+    }
+
+    async fn hash(&self, path: &Path) -> Result<ContentHash, PortError> {
+        let content = self.read(path).await?;
+        Ok(ContentHash::from_bytes(&content))
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> Result<(), PortError> {
+        self.add_dirs(path);
+        Ok(())
+    }
+
+    async fn delete(&self, path: &Path) -> Result<(), PortError> {
+        self.files
+            .lock()
+            .unwrap()
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| PortError::FileNotFound {
+                path: path.to_path_buf(),
+            })
+    }
+
+    async fn scaffold_deliverable(&self, deliverable: &Deliverable) -> Result<(), PortError> {
+        self.add_dirs(&deliverable.folder_path);
+        for doc_type in DocumentType::ALL {
+            let path = deliverable.folder_path.join(doc_type.filename());
+            if !self.files.lock().unwrap().contains_key(&path) {
+                self.put(&path, b"");
+            }
+        }
+        Ok(())
+    }
+
+    async fn watch(&self, _path: &Path) -> Result<FsChangeStream, PortError> {
+        Err(PortError::Internal {
+            message: "InMemoryWorkspace does not support watching".to_string(),
+        })
+    }
+
+    async fn commit_transaction(
+        &self,
+        transaction: WorkspaceTransaction,
+    ) -> Result<Vec<(PathBuf, ContentHash)>, PortError> {
+        for change in transaction.changes() {
+            if let StagedChange::Delete { path } = change {
+                if !self.files.lock().unwrap().contains_key(path) {
+                    return Err(PortError::FileNotFound { path: path.clone() });
+                }
+            }
+        }
+        let mut written = Vec::new();
+        for change in transaction.changes() {
+            match change {
+                StagedChange::Write { path, content } => {
+                    self.put(path, content);
+                    written.push((path.clone(), ContentHash::from_bytes(content)));
+                }
+                StagedChange::Delete { path } => {
+                    self.files.lock().unwrap().remove(path);
+                }
+            }
+        }
+        Ok(written)
+    }
+
+    async fn recover_transactions(&self) -> Result<Vec<String>, PortError> {
+        Ok(Vec::new())
+    }
+}
+
+/// A `GitPort` that records staged paths and commit messages.
+///
+/// Only staging, committing and configured `changed_paths` ranges are
+/// supported; other calls return `PortError::Internal` naming the method.
+#[derive(Default)]
+pub struct RecordingGit {
+    staged: Mutex<Vec<PathBuf>>,
+    commits: Mutex<Vec<String>>,
+    changes: HashMap<(CommitHash, CommitHash), Vec<PathBuf>>,
+}
+
+impl RecordingGit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report `paths` as changed between `from` and `to`.
+    pub fn with_changed_paths(
+        mut self,
+        from: &str,
+        to: &str,
+        paths: impl IntoIterator<Item = impl Into<PathBuf>>,
+    ) -> Self {
+        self.changes.insert(
+            (CommitHash::from_string(from), CommitHash::from_string(to)),
+            paths.into_iter().map(Into::into).collect(),
+        );
+        self
+    }
+
+    pub fn staged(&self) -> Vec<PathBuf> {
+        self.staged.lock().unwrap().clone()
+    }
+
+    pub fn commits(&self) -> Vec<String> {
+        self.commits.lock().unwrap().clone()
+    }
+}
+
+fn unsupported(method: &str) -> PortError {
+    PortError::Internal {
+        message: format!("RecordingGit does not support {}", method),
+    }
+}
+
+#[async_trait]
+impl GitPort for RecordingGit {
+    async fn stage(&self, paths: &[PathBuf]) -> Result<(), PortError> {
+        self.staged.lock().unwrap().extend_from_slice(paths);
+        Ok(())
+    }
+
+    async fn stage_all(&self) -> Result<(), PortError> {
+        Err(unsupported("stage_all"))
+    }
+
+    async fn commit(&self, message: &str, _author: &ActorId) -> Result<CommitHash, PortError> {
+        let mut commits = self.commits.lock().unwrap();
+        commits.push(message.to_string());
+        Ok(CommitHash::from_string(format!("commit-{}", commits.len())))
+    }
+
+    async fn head(&self) -> Result<CommitHash, PortError> {
+        Err(unsupported("head"))
+    }
+
+    async fn current_branch(&self) -> Result<String, PortError> {
+        Err(unsupported("current_branch"))
+    }
+
+    async fn create_branch(&self, _name: &str) -> Result<(), PortError> {
+        Err(unsupported("create_branch"))
+    }
+
+    async fn checkout(&self, _branch: &str) -> Result<(), PortError> {
+        Err(unsupported("checkout"))
+    }
+
+    async fn merge(&self, _branch: &str, _message: &str) -> Result<CommitHash, PortError> {
+        Err(unsupported("merge"))
+    }
+
+    async fn delete_branch(&self, _name: &str) -> Result<(), PortError> {
+        Err(unsupported("delete_branch"))
+    }
+
+    async fn log(&self, _path: Option<&Path>, _limit: usize) -> Result<Vec<CommitInfo>, PortError> {
+        Err(unsupported("log"))
+    }
+
+    async fn changed_paths(
+        &self,
+        from: &CommitHash,
+        to: &CommitHash,
+    ) -> Result<Vec<PathBuf>, PortError> {
+        self.changes
+            .get(&(from.clone(), to.clone()))
+            .cloned()
+            .ok_or_else(|| unsupported(&format!("changed_paths({}..{})", from, to)))
+    }
+
+    async fn tag(&self, _name: &str, _message: Option<&str>) -> Result<(), PortError> {
+        Err(unsupported("tag"))
+    }
+}
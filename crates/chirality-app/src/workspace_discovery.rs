@@ -0,0 +1,422 @@
+//! Workspace discovery - rebuilds domain entities from the folder tree.
+//!
+//! Filesystem IS the state: a project folder contains `PKG-###_Label`
+//! package folders, each containing `DEL-##.##_Label` deliverable folders.
+//! The scanner walks that tree through the `WorkspacePort` and reports
+//! anything it cannot place as a diagnostic instead of skipping it.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chirality_domain::{
+    ActorId, Deliverable, DeliverableFolder, DeliverableId, DocumentId, DocumentRef,
+    DocumentType, FolderKind, FolderName, Package, PackageFolder, PackageId, Project,
+    RevisionScheme, StatusFile,
+};
+use chirality_ports::{PortError, WorkspacePort};
+
+/// Entities rebuilt from a project folder.
+#[derive(Debug, Clone)]
+pub struct WorkspaceSnapshot {
+    pub project: Project,
+    pub packages: Vec<Package>,
+    pub deliverables: Vec<Deliverable>,
+    pub diagnostics: Vec<DiscoveryDiagnostic>,
+}
+
+impl WorkspaceSnapshot {
+    /// Find a package by id.
+    pub fn package(&self, id: &PackageId) -> Option<&Package> {
+        self.packages.iter().find(|p| &p.id == id)
+    }
+
+    /// Find a deliverable by id.
+    pub fn deliverable(&self, id: &DeliverableId) -> Option<&Deliverable> {
+        self.deliverables.iter().find(|d| &d.id == id)
+    }
+
+    /// Deliverables belonging to a package.
+    pub fn deliverables_in<'a>(
+        &'a self,
+        package_id: &'a PackageId,
+    ) -> impl Iterator<Item = &'a Deliverable> + 'a {
+        self.deliverables
+            .iter()
+            .filter(move |d| &d.package_id == package_id)
+    }
+}
+
+/// A folder the scanner could not map cleanly onto an entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryDiagnostic {
+    pub path: PathBuf,
+    pub kind: DiagnosticKind,
+}
+
+/// What was wrong with a discovered folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// Folder looks like a package or deliverable but its name does not parse.
+    MalformedFolder { expected: FolderKind, reason: String },
+    /// Deliverable folder found outside any package folder (not loaded).
+    OrphanedDeliverable { deliverable_id: DeliverableId },
+    /// Package folder nested inside another package (not loaded).
+    NestedPackage { package_id: PackageId },
+    /// Legacy deliverable number names a different package than its parent folder.
+    PackageMismatch {
+        deliverable_id: DeliverableId,
+        package_id: PackageId,
+    },
+    /// A second folder claims an id that was already discovered (not loaded).
+    DuplicateId { id: String, first_path: PathBuf },
+    /// Folder inside a package that is not a deliverable.
+    UnrecognizedFolder,
+    /// `_STATUS.md` exists but does not parse; the deliverable is loaded as OPEN.
+    InvalidStatusFile { reason: String },
+}
+
+/// Rebuilds `Project`, `Package` and `Deliverable` values from a workspace.
+pub struct WorkspaceScanner<W: WorkspacePort + ?Sized> {
+    workspace: Arc<W>,
+}
+
+impl<W: WorkspacePort + ?Sized> WorkspaceScanner<W> {
+    pub fn new(workspace: Arc<W>) -> Self {
+        Self { workspace }
+    }
+
+    /// Scan a project root, creating a fresh `Project` named after the folder.
+    pub async fn scan(&self, root: &Path) -> Result<WorkspaceSnapshot, PortError> {
+        let name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| root.display().to_string());
+        let project = Project::new(name, root.to_path_buf(), ActorId::system());
+        self.scan_project(project).await
+    }
+
+    /// Scan the workspace of an existing `Project`, keeping its identity.
+    pub async fn scan_project(&self, project: Project) -> Result<WorkspaceSnapshot, PortError> {
+        let mut snapshot = WorkspaceSnapshot {
+            project,
+            packages: Vec::new(),
+            deliverables: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let mut seen: Vec<(String, PathBuf)> = Vec::new();
+
+        let root = snapshot.project.workspace_path.clone();
+        for path in self.child_dirs(&root).await? {
+            match FolderName::parse(&folder_name(&path)) {
+                FolderName::Package(folder) => {
+                    if let Some(first) = claim(&mut seen, folder.id.as_str(), &path) {
+                        snapshot.diagnostics.push(DiscoveryDiagnostic {
+                            path,
+                            kind: DiagnosticKind::DuplicateId {
+                                id: folder.id.to_string(),
+                                first_path: first,
+                            },
+                        });
+                        continue;
+                    }
+                    let package = build_package(&snapshot.project, &folder, &path);
+                    self.scan_package(&package, folder.number, &path, &mut seen, &mut snapshot)
+                        .await?;
+                    snapshot.packages.push(package);
+                }
+                FolderName::Deliverable(folder) => {
+                    snapshot.diagnostics.push(DiscoveryDiagnostic {
+                        path,
+                        kind: DiagnosticKind::OrphanedDeliverable {
+                            deliverable_id: folder.id,
+                        },
+                    });
+                }
+                FolderName::Malformed { expected, reason } => {
+                    snapshot.diagnostics.push(DiscoveryDiagnostic {
+                        path,
+                        kind: DiagnosticKind::MalformedFolder { expected, reason },
+                    });
+                }
+                // Tool roots, execution folders and the like live beside packages.
+                FolderName::Unrecognized => {}
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    async fn scan_package(
+        &self,
+        package: &Package,
+        package_number: Option<u32>,
+        path: &Path,
+        seen: &mut Vec<(String, PathBuf)>,
+        snapshot: &mut WorkspaceSnapshot,
+    ) -> Result<(), PortError> {
+        for child in self.child_dirs(path).await? {
+            let name = folder_name(&child);
+            match FolderName::parse(&name) {
+                FolderName::Deliverable(folder) => {
+                    if let Some(first) = claim(seen, folder.id.as_str(), &child) {
+                        snapshot.diagnostics.push(DiscoveryDiagnostic {
+                            path: child,
+                            kind: DiagnosticKind::DuplicateId {
+                                id: folder.id.to_string(),
+                                first_path: first,
+                            },
+                        });
+                        continue;
+                    }
+                    if let (Some(expected), Some(actual)) = (package_number, folder.package_number) {
+                        if expected != actual {
+                            snapshot.diagnostics.push(DiscoveryDiagnostic {
+                                path: child.clone(),
+                                kind: DiagnosticKind::PackageMismatch {
+                                    deliverable_id: folder.id.clone(),
+                                    package_id: package.id.clone(),
+                                },
+                            });
+                        }
+                    }
+                    let scheme = snapshot.project.revision_scheme;
+                    let deliverable = self
+                        .load_deliverable(package, &folder, &child, scheme, &mut snapshot.diagnostics)
+                        .await?;
+                    snapshot.deliverables.push(deliverable);
+                }
+                FolderName::Package(folder) => {
+                    snapshot.diagnostics.push(DiscoveryDiagnostic {
+                        path: child,
+                        kind: DiagnosticKind::NestedPackage {
+                            package_id: folder.id,
+                        },
+                    });
+                }
+                FolderName::Malformed { expected, reason } => {
+                    snapshot.diagnostics.push(DiscoveryDiagnostic {
+                        path: child,
+                        kind: DiagnosticKind::MalformedFolder { expected, reason },
+                    });
+                }
+                FolderName::Unrecognized if is_hidden_or_tooling(&name) => {}
+                FolderName::Unrecognized => {
+                    snapshot.diagnostics.push(DiscoveryDiagnostic {
+                        path: child,
+                        kind: DiagnosticKind::UnrecognizedFolder,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn load_deliverable(
+        &self,
+        package: &Package,
+        folder: &DeliverableFolder,
+        path: &Path,
+        scheme: RevisionScheme,
+        diagnostics: &mut Vec<DiscoveryDiagnostic>,
+    ) -> Result<Deliverable, PortError> {
+        let label = folder
+            .label
+            .clone()
+            .unwrap_or_else(|| folder.id.to_string());
+        let mut deliverable = Deliverable::new(package.id.clone(), label, path.to_path_buf())
+            .with_revision_scheme(scheme);
+        deliverable.id = folder.id.clone();
+
+        let present: HashSet<String> = self
+            .workspace
+            .list_dir(path)
+            .await?
+            .iter()
+            .map(|p| folder_name(p))
+            .collect();
+        for document_type in DocumentType::ALL {
+            if present.contains(document_type.filename()) {
+                deliverable.add_document(DocumentRef {
+                    id: DocumentId::for_file(&deliverable.id, document_type.filename()),
+                    document_type,
+                    file_path: path.join(document_type.filename()),
+                });
+            }
+        }
+
+        if present.contains(DocumentType::Status.filename()) {
+            let status_path = path.join(DocumentType::Status.filename());
+            let content = self.workspace.read(&status_path).await?;
+            match StatusFile::parse(&String::from_utf8_lossy(&content)) {
+                Ok(status) => {
+                    deliverable.state = status.state;
+                    deliverable = deliverable.with_history(status.history);
+                    if let Some(revision) = status.revision {
+                        deliverable = deliverable.with_revisions(revision, status.issued_revisions);
+                    }
+                }
+                Err(e) => diagnostics.push(DiscoveryDiagnostic {
+                    path: status_path,
+                    kind: DiagnosticKind::InvalidStatusFile {
+                        reason: e.to_string(),
+                    },
+                }),
+            }
+        }
+        Ok(deliverable)
+    }
+
+    async fn child_dirs(&self, path: &Path) -> Result<Vec<PathBuf>, PortError> {
+        let mut dirs = Vec::new();
+        for entry in self.workspace.list_dir(path).await? {
+            if self.workspace.is_dir(&entry).await? {
+                dirs.push(entry);
+            }
+        }
+        dirs.sort();
+        Ok(dirs)
+    }
+}
+
+fn build_package(project: &Project, folder: &PackageFolder, path: &Path) -> Package {
+    Package {
+        id: folder.id.clone(),
+        project_id: project.id.clone(),
+        label: folder.label.clone().unwrap_or_else(|| folder.id.to_string()),
+        scope_items: Vec::new(),
+        folder_name: folder_name(path),
+    }
+}
+
+/// Record an id as seen, returning the earlier path if it was already claimed.
+fn claim(seen: &mut Vec<(String, PathBuf)>, id: &str, path: &Path) -> Option<PathBuf> {
+    if let Some((_, first)) = seen.iter().find(|(s, _)| s == id) {
+        return Some(first.clone());
+    }
+    seen.push((id.to_string(), path.to_path_buf()));
+    None
+}
+
+fn folder_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn is_hidden_or_tooling(name: &str) -> bool {
+    name.starts_with('.') || name.starts_with('_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryWorkspace;
+    use chirality_domain::DeliverableState;
+
+    fn sample_workspace() -> InMemoryWorkspace {
+        InMemoryWorkspace::new()
+            .with_file("/proj/PKG-001_Civil/DEL-01.01_Foundation/Datasheet.md", "# DS")
+            .with_file(
+                "/proj/PKG-001_Civil/DEL-01.01_Foundation/_STATUS.md",
+                "<!-- chirality:status:begin -->\n- **State:** CHECKING\n<!-- chirality:status:end -->\n",
+            )
+            .with_file("/proj/PKG-001_Civil/DEL-01.02/Specification.md", "")
+            .with_file("/proj/PKG-001_Civil/DEL-02.01_Misplaced/Guidance.md", "")
+            .with_dir("/proj/PKG-001_Civil/notes")
+            .with_dir("/proj/PKG-001_Civil/_scratch")
+            .with_dir("/proj/PKG-002_Mechanical")
+            .with_dir("/proj/PKG-x_Broken")
+            .with_dir("/proj/DEL-03.01_Orphan")
+            .with_dir("/proj/execution/_Aggregation")
+    }
+
+    #[tokio::test]
+    async fn rebuilds_packages_and_deliverables() {
+        let scanner = WorkspaceScanner::new(Arc::new(sample_workspace()));
+        let snapshot = scanner.scan(Path::new("/proj")).await.unwrap();
+
+        assert_eq!(snapshot.project.name, "proj");
+        assert_eq!(snapshot.packages.len(), 2);
+        let civil = snapshot.package(&PackageId::from_legacy(1)).unwrap();
+        assert_eq!(civil.label, "Civil");
+        assert_eq!(civil.folder_name, "PKG-001_Civil");
+        assert_eq!(snapshot.deliverables_in(&civil.id).count(), 3);
+
+        let foundation = snapshot
+            .deliverable(&DeliverableId::from_legacy(1, 1))
+            .unwrap();
+        assert_eq!(foundation.label, "Foundation");
+        assert_eq!(foundation.state, DeliverableState::Checking);
+        let types: Vec<_> = foundation.documents.iter().map(|d| d.document_type).collect();
+        assert_eq!(types, vec![DocumentType::Datasheet, DocumentType::Status]);
+
+        let rescanned = scanner.scan(Path::new("/proj")).await.unwrap();
+        let ids = |snapshot: &WorkspaceSnapshot| -> Vec<DocumentId> {
+            snapshot.deliverables.iter().flat_map(|d| d.documents.iter().map(|r| r.id.clone())).collect()
+        };
+        assert_eq!(ids(&rescanned), ids(&snapshot));
+        assert_eq!(foundation.documents[0].id.as_str(), "doc:DEL-01.01/Datasheet.md");
+    }
+
+    #[tokio::test]
+    async fn reports_malformed_and_orphaned_folders() {
+        let scanner = WorkspaceScanner::new(Arc::new(sample_workspace()));
+        let snapshot = scanner.scan(Path::new("/proj")).await.unwrap();
+        let kinds: Vec<_> = snapshot.diagnostics.iter().map(|d| &d.kind).collect();
+
+        assert!(kinds.iter().any(|k| matches!(
+            k,
+            DiagnosticKind::MalformedFolder {
+                expected: FolderKind::Package,
+                ..
+            }
+        )));
+        assert!(kinds.iter().any(|k| matches!(
+            k,
+            DiagnosticKind::OrphanedDeliverable { deliverable_id }
+                if deliverable_id.as_str() == "DEL-03.01"
+        )));
+        assert!(kinds.iter().any(|k| matches!(
+            k,
+            DiagnosticKind::PackageMismatch { deliverable_id, .. }
+                if deliverable_id.as_str() == "DEL-02.01"
+        )));
+        assert!(snapshot.diagnostics.iter().any(|d| {
+            d.kind == DiagnosticKind::UnrecognizedFolder
+                && d.path == Path::new("/proj/PKG-001_Civil/notes")
+        }));
+        assert_eq!(snapshot.diagnostics.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn reports_unparseable_status_file() {
+        let workspace = InMemoryWorkspace::new().with_file(
+            "/proj/PKG-001/DEL-01.01/_STATUS.md",
+            "<!-- chirality:status:begin -->\n- **State:** DONE\n<!-- chirality:status:end -->\n",
+        );
+        let scanner = WorkspaceScanner::new(Arc::new(workspace));
+        let snapshot = scanner.scan(Path::new("/proj")).await.unwrap();
+
+        assert_eq!(snapshot.deliverables[0].state, DeliverableState::Open);
+        assert!(matches!(
+            snapshot.diagnostics[0].kind,
+            DiagnosticKind::InvalidStatusFile { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn reports_duplicate_ids() {
+        let workspace = InMemoryWorkspace::new()
+            .with_dir("/proj/PKG-001_Civil")
+            .with_dir("/proj/PKG-1_Civil_Again");
+        let scanner = WorkspaceScanner::new(Arc::new(workspace));
+        let snapshot = scanner.scan(Path::new("/proj")).await.unwrap();
+
+        assert_eq!(snapshot.packages.len(), 1);
+        assert!(matches!(
+            &snapshot.diagnostics[0].kind,
+            DiagnosticKind::DuplicateId { id, .. } if id == "PKG-001"
+        ));
+    }
+}
@@ -0,0 +1,426 @@
+//! Agent definitions parsed from `AGENT_*.md` instruction files.
+//!
+//! The file is the agent's system prompt; its header carries the metadata
+//! the runtime needs before the prompt is sent:
+//!
+//! ```text
+//! # AGENT_4_DOCUMENTS
+//!
+//! AGENT_TYPE: 2
+//! AGENT_CLASS: TASK
+//! WRITE_SCOPE: deliverable-local
+//! REQUIRED_INPUTS: deliverable_id:string
+//! OPTIONAL_INPUTS: notes
+//! MIN_OUTPUTS: 4
+//!
+//! ---
+//! (instructions)
+//! ```
+//!
+//! The header ends at the first `---` rule or `## ` heading. Keys may also be
+//! written as bullets (`- **AGENT_CLASS:** TASK`). Unknown keys are kept.
+//! Input lists are comma-separated; `a|b` accepts either key and `key:type`
+//! constrains the value's JSON type.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::brief_schema::{BriefSchema, InputRule, InputType};
+use crate::entities::{AgentClass, AgentType, DeliverableId};
+use crate::error::DomainError;
+use crate::write_guard::WriteScope;
+
+/// Metadata and instructions for one agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentDefinition {
+    /// Agent name, from the file name (`AGENT_4_DOCUMENTS.md` → `4_DOCUMENTS`).
+    pub name: String,
+    pub agent_type: AgentType,
+    pub agent_class: AgentClass,
+    pub write_scope: WriteScopeTemplate,
+    /// Brief schema built from REQUIRED_INPUTS, OPTIONAL_INPUTS and MIN_OUTPUTS;
+    /// `None` if the header declares none of them.
+    pub brief_schema: Option<BriefSchema>,
+    /// Header keys the runtime does not interpret, in file order.
+    pub extra_fields: Vec<(String, String)>,
+    /// Full file content, used as the system prompt.
+    pub instructions: String,
+}
+
+impl AgentDefinition {
+    /// Agent name for an instruction file name, if it follows `AGENT_<NAME>.md`.
+    pub fn name_from_filename(file_name: &str) -> Option<&str> {
+        file_name
+            .strip_prefix("AGENT_")?
+            .strip_suffix(".md")
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Parse an instruction file. `file_name` names the agent and errors.
+    pub fn parse(file_name: &str, content: &str) -> Result<Self, DomainError> {
+        let error = |line: usize, reason: String| DomainError::InvalidAgentDefinition {
+            file: file_name.to_string(),
+            line,
+            reason,
+        };
+        let name = Self::name_from_filename(file_name)
+            .ok_or_else(|| error(0, "file name must look like AGENT_<NAME>.md".to_string()))?
+            .to_string();
+
+        let mut agent_type = None;
+        let mut agent_class = None;
+        let mut write_scope = None;
+        let mut brief_schema: Option<BriefSchema> = None;
+        let mut extra_fields = Vec::new();
+        let mut header_end = 1;
+
+        for (index, raw) in content.lines().enumerate() {
+            let line = index + 1;
+            let trimmed = raw.trim();
+            header_end = line;
+            if trimmed == "---" || trimmed.starts_with("## ") {
+                break;
+            }
+            if trimmed.is_empty() || trimmed.starts_with("# ") {
+                continue;
+            }
+            let Some((key, value)) = parse_header_field(trimmed) else {
+                continue;
+            };
+            match key.as_str() {
+                "AGENT_TYPE" => agent_type = Some(parse_agent_type(&value).map_err(|r| error(line, r))?),
+                "AGENT_CLASS" => {
+                    agent_class = Some(parse_agent_class(&value).map_err(|r| error(line, r))?)
+                }
+                "WRITE_SCOPE" => {
+                    write_scope = Some(WriteScopeTemplate::parse(&value).map_err(|r| error(line, r))?)
+                }
+                "REQUIRED_INPUTS" | "OPTIONAL_INPUTS" => {
+                    let required = key == "REQUIRED_INPUTS";
+                    brief_schema
+                        .get_or_insert_with(BriefSchema::default)
+                        .inputs
+                        .extend(parse_input_rules(&value, required).map_err(|r| error(line, r))?)
+                }
+                "MIN_OUTPUTS" => {
+                    brief_schema.get_or_insert_with(BriefSchema::default).min_output_contract = value
+                        .parse()
+                        .map_err(|_| error(line, format!("MIN_OUTPUTS must be a number, found {:?}", value)))?
+                }
+                _ => extra_fields.push((key, value)),
+            }
+        }
+
+        let missing = |key: &str| error(header_end, format!("header is missing {}", key));
+        Ok(Self {
+            name,
+            agent_type: agent_type.ok_or_else(|| missing("AGENT_TYPE"))?,
+            agent_class: agent_class.ok_or_else(|| missing("AGENT_CLASS"))?,
+            write_scope: write_scope.ok_or_else(|| missing("WRITE_SCOPE"))?,
+            brief_schema,
+            extra_fields,
+            instructions: content.to_string(),
+        })
+    }
+
+    /// Value of a header key the runtime does not interpret.
+    pub fn extra_field(&self, key: &str) -> Option<&str> {
+        self.extra_fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// `deliverable_id:string, package_id|project_id`
+fn parse_input_rules(value: &str, required: bool) -> Result<Vec<InputRule>, String> {
+    let mut rules = Vec::new();
+    for item in value.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+        let (keys, value_type) = match item.split_once(':') {
+            Some((keys, ty)) => (
+                keys,
+                InputType::parse(ty).ok_or_else(|| format!("unknown input type {:?}", ty.trim()))?,
+            ),
+            None => (item, InputType::Any),
+        };
+        let keys: Vec<&str> = keys.split('|').map(str::trim).collect();
+        if let Some(bad) = keys.iter().find(|k| !is_input_key(k)) {
+            return Err(format!("invalid input key {:?}", bad));
+        }
+        let rule = if required {
+            InputRule::required(&keys)
+        } else {
+            InputRule::optional(&keys)
+        };
+        rules.push(rule.with_type(value_type));
+    }
+    Ok(rules)
+}
+
+fn is_input_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A write scope whose paths are filled in when a session starts.
+///
+/// Written as one or more rules joined by `+`, paths relative to the
+/// workspace root: `tool-root(execution/_Aggregation) + repo-metadata(_COORDINATION.md)`.
+/// Absolute paths and `..` are rejected so a scope can never reach outside
+/// the workspace it is instantiated against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteScopeTemplate {
+    None,
+    DeliverableLocal,
+    ToolRoot { root: PathBuf },
+    RepoMetadata { files: Vec<PathBuf> },
+    Glob { pattern: String },
+    Union(Vec<WriteScopeTemplate>),
+}
+
+impl WriteScopeTemplate {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let rules = value
+            .split('+')
+            .map(|rule| Self::parse_rule(rule.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+        match <[_; 1]>::try_from(rules) {
+            Ok([single]) => Ok(single),
+            Err(rules) => Ok(WriteScopeTemplate::Union(rules)),
+        }
+    }
+
+    fn parse_rule(rule: &str) -> Result<Self, String> {
+        let (name, args) = match rule.split_once('(') {
+            Some((name, rest)) => {
+                let args = rest
+                    .strip_suffix(')')
+                    .ok_or_else(|| format!("unclosed parenthesis in {:?}", rule))?;
+                (name.trim(), Some(args))
+            }
+            None => (rule, None),
+        };
+        let name = name.to_ascii_lowercase().replace('_', "-");
+        let paths = |args: Option<&str>| -> Result<Vec<PathBuf>, String> {
+            let paths: Vec<PathBuf> = args
+                .unwrap_or("")
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(workspace_relative)
+                .collect::<Result<_, _>>()?;
+            if paths.is_empty() {
+                return Err(format!("{} needs at least one path", name));
+            }
+            Ok(paths)
+        };
+        match name.as_str() {
+            "none" | "read-only" => Ok(WriteScopeTemplate::None),
+            "deliverable-local" => Ok(WriteScopeTemplate::DeliverableLocal),
+            "tool-root" | "tool-root-only" => match paths(args)?.as_slice() {
+                [root] => Ok(WriteScopeTemplate::ToolRoot { root: root.clone() }),
+                _ => Err("tool-root takes exactly one path".to_string()),
+            },
+            "repo-metadata" | "repo-metadata-only" => Ok(WriteScopeTemplate::RepoMetadata {
+                files: paths(args)?,
+            }),
+            "glob" => {
+                let pattern = args
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .ok_or("glob needs a pattern")?;
+                workspace_relative(pattern)?;
+                Ok(WriteScopeTemplate::Glob {
+                    pattern: pattern.to_string(),
+                })
+            }
+            "" => Err("empty write scope rule".to_string()),
+            other => Err(format!("unknown write scope {:?}", other)),
+        }
+    }
+
+    /// Resolve against a workspace root and, for deliverable-local rules, a deliverable.
+    pub fn instantiate(
+        &self,
+        workspace_root: &Path,
+        deliverable: Option<(&DeliverableId, &Path)>,
+    ) -> Result<WriteScope, DomainError> {
+        Ok(match self {
+            WriteScopeTemplate::None => WriteScope::None,
+            WriteScopeTemplate::DeliverableLocal => {
+                let (id, path) = deliverable.ok_or_else(|| DomainError::PreconditionFailed {
+                    message: "deliverable-local write scope needs a deliverable".to_string(),
+                })?;
+                WriteScope::DeliverableLocal {
+                    deliverable_id: id.clone(),
+                    deliverable_path: path.to_path_buf(),
+                }
+            }
+            WriteScopeTemplate::ToolRoot { root } => WriteScope::ToolRootOnly {
+                root_path: workspace_root.join(root),
+            },
+            WriteScopeTemplate::RepoMetadata { files } => WriteScope::RepoMetadataOnly {
+                allowed_files: files.iter().map(|f| workspace_root.join(f)).collect(),
+            },
+            WriteScopeTemplate::Glob { pattern } => WriteScope::Glob {
+                root: workspace_root.to_path_buf(),
+                pattern: pattern.clone(),
+            },
+            WriteScopeTemplate::Union(rules) => WriteScope::Composite {
+                allow: rules
+                    .iter()
+                    .map(|r| r.instantiate(workspace_root, deliverable))
+                    .collect::<Result<_, _>>()?,
+                deny: Vec::new(),
+            },
+        })
+    }
+}
+
+/// A path that stays inside whatever root it is joined to.
+fn workspace_relative(path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path);
+    for component in path.components() {
+        match component {
+            Component::Normal(_) | Component::CurDir => {}
+            Component::ParentDir => return Err(format!("{:?} must not contain `..`", path)),
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(format!("{:?} must be relative to the workspace root", path))
+            }
+        }
+    }
+    Ok(path)
+}
+
+/// `KEY: value`, `- KEY: value` or `- **KEY:** value`, with an upper-case key.
+fn parse_header_field(line: &str) -> Option<(String, String)> {
+    let line = line.strip_prefix("- ").unwrap_or(line).trim();
+    let (key, value) = if let Some(rest) = line.strip_prefix("**") {
+        let (key, value) = rest.split_once(":**")?;
+        (key, value)
+    } else {
+        line.split_once(':')?
+    };
+    let key = key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_') {
+        return None;
+    }
+    Some((key.to_string(), value.trim().to_string()))
+}
+
+/// `2`, `TYPE 2`, `TYPE_2` or the type name.
+fn parse_agent_type(value: &str) -> Result<AgentType, String> {
+    let normalized = value.trim().to_ascii_uppercase();
+    let normalized = normalized
+        .strip_prefix("TYPE")
+        .map(|rest| rest.trim_start_matches([' ', '_']))
+        .unwrap_or(normalized.as_str());
+    match normalized {
+        "0" | "ARCHITECT" => Ok(AgentType::Architect),
+        "1" | "MANAGER" => Ok(AgentType::Manager),
+        "2" | "SPECIALIST" => Ok(AgentType::Specialist),
+        _ => Err(format!(
+            "unknown AGENT_TYPE {:?} (expected 0/1/2 or ARCHITECT/MANAGER/SPECIALIST)",
+            value
+        )),
+    }
+}
+
+fn parse_agent_class(value: &str) -> Result<AgentClass, String> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "PERSONA" => Ok(AgentClass::Persona),
+        "TASK" => Ok(AgentClass::Task),
+        _ => Err(format!("unknown AGENT_CLASS {:?} (expected PERSONA or TASK)", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AGGREGATION: &str = "# AGENT_AGGREGATION\n\n\
+        - **AGENT_TYPE:** TYPE 2\n\
+        - **AGENT_CLASS:** TASK\n\
+        - **WRITE_SCOPE:** tool-root(execution/_Aggregation) + repo-metadata(_COORDINATION.md)\n\
+        - **REQUIRED_INPUTS:** project_id:string\n\
+        - **MIN_OUTPUTS:** 1\n\
+        - **BLOCKING:** never\n\
+        \n---\n\nAggregate things. AGENT_CLASS: ignored here\n";
+
+    #[test]
+    fn parses_header_and_keeps_instructions() {
+        let definition = AgentDefinition::parse("AGENT_AGGREGATION.md", AGGREGATION).unwrap();
+        assert_eq!(definition.name, "AGGREGATION");
+        assert_eq!(definition.agent_type, AgentType::Specialist);
+        assert_eq!(definition.agent_class, AgentClass::Task);
+        assert_eq!(definition.brief_schema.as_ref().unwrap().inputs[0].keys, ["project_id"]);
+        assert_eq!(definition.brief_schema.as_ref().unwrap().inputs[0].value_type, InputType::String);
+        assert_eq!(definition.brief_schema.as_ref().unwrap().min_output_contract, 1);
+        assert_eq!(definition.extra_field("BLOCKING"), Some("never"));
+        assert_eq!(definition.instructions, AGGREGATION);
+
+        let scope = definition
+            .write_scope
+            .instantiate(Path::new("/proj"), None)
+            .unwrap();
+        assert_eq!(
+            scope.describe(),
+            "Composite(allow: [ToolRootOnly(/proj/execution/_Aggregation), \
+             RepoMetadataOnly([\"/proj/_COORDINATION.md\"])], deny: [])"
+        );
+    }
+
+    #[test]
+    fn plain_header_with_alternative_inputs() {
+        let content = "AGENT_TYPE: 2\nAGENT_CLASS: task\nWRITE_SCOPE: deliverable-local\n\
+                       REQUIRED_INPUTS: package_id | project_id\n## Role\n";
+        let definition = AgentDefinition::parse("AGENT_PREPARATION.md", content).unwrap();
+        assert_eq!(definition.write_scope, WriteScopeTemplate::DeliverableLocal);
+        assert_eq!(definition.brief_schema.as_ref().unwrap().inputs[0].keys, ["package_id", "project_id"]);
+        assert!(definition.brief_schema.as_ref().unwrap().inputs[0].required);
+        assert!(definition
+            .write_scope
+            .instantiate(Path::new("/proj"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_line() {
+        let content = "# AGENT_X\nAGENT_TYPE: 2\nAGENT_CLASS: ROBOT\n";
+        match AgentDefinition::parse("AGENT_X.md", content) {
+            Err(DomainError::InvalidAgentDefinition { file, line, reason }) => {
+                assert_eq!(file, "AGENT_X.md");
+                assert_eq!(line, 3);
+                assert!(reason.contains("ROBOT"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let content = "AGENT_TYPE: 2\nAGENT_CLASS: TASK\n---\nBody\n";
+        assert!(matches!(
+            AgentDefinition::parse("AGENT_X.md", content),
+            Err(DomainError::InvalidAgentDefinition { line: 3, ref reason, .. })
+                if reason.contains("WRITE_SCOPE")
+        ));
+        assert!(AgentDefinition::parse("NOTES.md", content).is_err());
+    }
+
+    #[test]
+    fn write_scope_paths_cannot_leave_the_workspace() {
+        for value in [
+            "tool-root(/etc)",
+            "repo-metadata(_COORDINATION.md, /home/user/.bashrc)",
+            "tool-root(execution/../../outside)",
+            "repo-metadata(../_COORDINATION.md)",
+            "glob(../**/*.md)",
+            "glob(/**/*.md)",
+        ] {
+            assert!(WriteScopeTemplate::parse(value).is_err(), "{}", value);
+        }
+        assert!(WriteScopeTemplate::parse("tool-root(./execution/_Aggregation)").is_ok());
+
+        let content = "AGENT_TYPE: 2\nAGENT_CLASS: TASK\nWRITE_SCOPE: tool-root(../outside)\n---\n";
+        assert!(matches!(
+            AgentDefinition::parse("AGENT_X.md", content),
+            Err(DomainError::InvalidAgentDefinition { line: 3, ref reason, .. }) if reason.contains("..")
+        ));
+    }
+}
@@ -0,0 +1,348 @@
+//! Brief generation from deliverable context.
+//!
+//! A `BriefTemplate` holds the wording for one agent; `BriefBuilder` fills it
+//! from a `Deliverable` and the contents of its `_CONTEXT.md` and
+//! `_REFERENCES.md`. Template text may use these placeholders:
+//!
+//! | Placeholder          | Value                                   |
+//! |----------------------|-----------------------------------------|
+//! | `{agent}`            | Agent name                              |
+//! | `{deliverable_id}`   | Deliverable id                          |
+//! | `{label}`            | Deliverable label                       |
+//! | `{package_id}`       | Package id                              |
+//! | `{revision}`         | Current revision                        |
+//! | `{deliverable_type}` | Deliverable type, or empty              |
+//! | `{discipline}`       | Discipline, or empty                    |
+//!
+//! Placeholders are substituted in one pass, so a value that itself looks
+//! like a placeholder is left as written.
+//!
+//! Templates for the shipped agents are built in; a JSON file with the same
+//! shape overrides them per agent.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+use crate::agent_definition::AgentDefinition;
+use crate::dependencies::is_table_separator;
+use crate::entities::{Deliverable, DocumentType, SessionBrief};
+use crate::error::DomainError;
+
+/// Templates for the agents shipped with chirality-app.
+const BUILTIN_TEMPLATES: &str = include_str!("brief_templates.json");
+
+/// Wording of a generated brief for one agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BriefTemplate {
+    pub task_definition: String,
+    #[serde(default)]
+    pub scope_description: String,
+    #[serde(default)]
+    pub constraints: Vec<String>,
+    #[serde(default)]
+    pub success_criteria: Vec<String>,
+    /// Put the four core documents and anticipated artifacts in the output contract.
+    #[serde(default = "default_true")]
+    pub include_outputs: bool,
+    /// Extra output contract entries, after the generated ones.
+    #[serde(default)]
+    pub outputs: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for BriefTemplate {
+    fn default() -> Self {
+        Self {
+            task_definition: "Run {agent} for {deliverable_id} ({label})".to_string(),
+            scope_description: "Deliverable {deliverable_id} in {package_id}".to_string(),
+            constraints: Vec::new(),
+            success_criteria: Vec::new(),
+            include_outputs: true,
+            outputs: Vec::new(),
+        }
+    }
+}
+
+/// Brief templates keyed by agent name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BriefTemplates {
+    templates: BTreeMap<String, BriefTemplate>,
+}
+
+impl BriefTemplates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Templates for the agents shipped with chirality-app.
+    pub fn builtin() -> Self {
+        Self::from_json(BUILTIN_TEMPLATES).expect("built-in brief templates are valid")
+    }
+
+    /// Parse a JSON file mapping agent names to templates.
+    pub fn from_json(content: &str) -> Result<Self, DomainError> {
+        serde_json::from_str(content).map_err(|e| DomainError::InvalidBrief {
+            reason: format!("invalid brief template config: {}", e),
+        })
+    }
+
+    /// Add or replace the template for an agent.
+    pub fn insert(&mut self, agent_name: impl Into<String>, template: BriefTemplate) {
+        self.templates.insert(agent_name.into(), template);
+    }
+
+    /// Overlay `other` on top of these templates, replacing per agent.
+    pub fn merge(&mut self, other: BriefTemplates) {
+        self.templates.extend(other.templates);
+    }
+
+    pub fn get(&self, agent_name: &str) -> Option<&BriefTemplate> {
+        self.templates.get(agent_name)
+    }
+}
+
+/// Builds a `SessionBrief` for one agent from deliverable context.
+#[derive(Debug, Clone)]
+pub struct BriefBuilder<'a> {
+    agent: &'a AgentDefinition,
+    template: BriefTemplate,
+    context: Option<String>,
+    references: Option<String>,
+}
+
+impl<'a> BriefBuilder<'a> {
+    /// Use the agent's template from `templates`, or the generic default.
+    pub fn new(agent: &'a AgentDefinition, templates: &BriefTemplates) -> Self {
+        Self {
+            agent,
+            template: templates.get(&agent.name).cloned().unwrap_or_default(),
+            context: None,
+            references: None,
+        }
+    }
+
+    pub fn with_template(mut self, template: BriefTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Contents of the deliverable's `_CONTEXT.md`.
+    pub fn with_context(mut self, content: impl Into<String>) -> Self {
+        self.context = Some(content.into());
+        self
+    }
+
+    /// Contents of the deliverable's `_REFERENCES.md`.
+    pub fn with_references(mut self, content: impl Into<String>) -> Self {
+        self.references = Some(content.into());
+        self
+    }
+
+    pub fn build(&self, deliverable: &Deliverable) -> SessionBrief {
+        let fill = |text: &str| self.fill(text, deliverable);
+
+        let mut output_contract = Vec::new();
+        if self.template.include_outputs {
+            output_contract.extend(
+                DocumentType::ALL
+                    .into_iter()
+                    .filter(DocumentType::is_core)
+                    .map(|t| t.filename().to_string()),
+            );
+            output_contract.extend(deliverable.anticipated_artifacts.iter().cloned());
+        }
+        for output in &self.template.outputs {
+            let output = fill(output);
+            if !output_contract.contains(&output) {
+                output_contract.push(output);
+            }
+        }
+
+        let mut inputs = Map::new();
+        inputs.insert("deliverable_id".into(), json!(deliverable.id.as_str()));
+        inputs.insert("package_id".into(), json!(deliverable.package_id.as_str()));
+        inputs.insert("revision".into(), json!(deliverable.revision.as_str()));
+        if let Some(context) = self.context.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            inputs.insert("context".into(), json!(context));
+        }
+        if let Some(references) = &self.references {
+            let references = reference_entries(references);
+            if !references.is_empty() {
+                inputs.insert("references".into(), json!(references));
+            }
+        }
+
+        SessionBrief {
+            task_definition: fill(&self.template.task_definition),
+            scope_description: fill(&self.template.scope_description),
+            output_contract,
+            constraints: self.template.constraints.iter().map(|c| fill(c)).collect(),
+            success_criteria: self.template.success_criteria.iter().map(|c| fill(c)).collect(),
+            inputs: Value::Object(inputs),
+        }
+    }
+
+    fn fill(&self, text: &str, deliverable: &Deliverable) -> String {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let values = [
+            ("agent", self.agent.name.clone()),
+            ("deliverable_id", deliverable.id.to_string()),
+            ("label", deliverable.label.clone()),
+            ("package_id", deliverable.package_id.to_string()),
+            ("revision", deliverable.revision.to_string()),
+            ("deliverable_type", optional(&deliverable.deliverable_type)),
+            ("discipline", optional(&deliverable.discipline)),
+        ];
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            let candidate = &rest[open + 1..];
+            let value = candidate.find('}').and_then(|close| {
+                let name = &candidate[..close];
+                values
+                    .iter()
+                    .find(|(placeholder, _)| *placeholder == name)
+                    .map(|(_, value)| (value, close))
+            });
+            match value {
+                Some((value, close)) => {
+                    out.push_str(value);
+                    rest = &candidate[close + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = candidate;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Reference entries from `_REFERENCES.md`: list items, or table rows
+/// (first cell) when the file has no list. A table's header is the row
+/// directly above its `|---|` separator.
+fn reference_entries(content: &str) -> Vec<String> {
+    let items: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")))
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect();
+    if !items.is_empty() {
+        return items;
+    }
+    let lines: Vec<&str> = content.lines().map(str::trim).collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with('|') && !is_table_separator(line))
+        .filter(|(i, _)| !lines.get(i + 1).is_some_and(|next| is_table_separator(next)))
+        .filter_map(|(_, line)| line.trim_matches('|').split('|').next().map(str::trim))
+        .filter(|cell| !cell.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brief_schema::BriefSchemas;
+    use crate::entities::PackageId;
+    use std::path::PathBuf;
+
+    fn agent(name: &str) -> AgentDefinition {
+        AgentDefinition::parse(
+            &format!("AGENT_{}.md", name),
+            "AGENT_TYPE: 2\nAGENT_CLASS: TASK\nWRITE_SCOPE: deliverable-local\n",
+        )
+        .unwrap()
+    }
+
+    fn deliverable() -> Deliverable {
+        let mut deliverable =
+            Deliverable::new(PackageId::from_legacy(1), "Foundation", PathBuf::from("DEL-01.01"))
+                .with_legacy_id(1, 1);
+        deliverable.anticipated_artifacts = vec!["calcs/loads.xlsx".into()];
+        deliverable
+    }
+
+    #[test]
+    fn builds_four_documents_brief_from_context() {
+        let agent = agent("4_DOCUMENTS");
+        let brief = BriefBuilder::new(&agent, &BriefTemplates::builtin())
+            .with_context("# Context\nSpread footing on clay.\n")
+            .with_references("# References\n- Geotech report GR-12\n- EN 1997-1\n")
+            .build(&deliverable());
+
+        assert!(brief.task_definition.starts_with("Generate the four documents for DEL-01.01 (Foundation)"));
+        assert_eq!(brief.scope_description, "Deliverable DEL-01.01 in PKG-001; write only inside its folder");
+        assert_eq!(
+            brief.output_contract,
+            ["Datasheet.md", "Specification.md", "Guidance.md", "Procedure.md", "calcs/loads.xlsx"]
+        );
+        assert_eq!(brief.inputs["context"], "# Context\nSpread footing on clay.");
+        assert_eq!(brief.inputs["references"], json!(["Geotech report GR-12", "EN 1997-1"]));
+        assert!(agent.brief_schema.is_none());
+        assert!(BriefSchemas::builtin().validate("4_DOCUMENTS", &brief).is_ok());
+    }
+
+    #[test]
+    fn overrides_replace_builtin_template() {
+        let agent = agent("4_DOCUMENTS");
+        let mut templates = BriefTemplates::builtin();
+        templates.merge(
+            BriefTemplates::from_json(
+                r#"{ "4_DOCUMENTS": {
+                    "task_definition": "Revise {label} to {revision}",
+                    "include_outputs": false,
+                    "outputs": ["{deliverable_id}-notes.md"]
+                } }"#,
+            )
+            .unwrap(),
+        );
+
+        let brief = BriefBuilder::new(&agent, &templates)
+            .with_references("| Ref | Title |\n|---|---|\n| GR-12 | Geotech |\n")
+            .build(&deliverable());
+        assert_eq!(brief.task_definition, "Revise Foundation to A");
+        assert_eq!(brief.output_contract, ["DEL-01.01-notes.md"]);
+        assert_eq!(brief.inputs["references"], json!(["GR-12"]));
+        assert!(brief.inputs.get("context").is_none());
+    }
+
+    #[test]
+    fn fills_placeholders_once_and_reads_headerless_tables() {
+        let agent = agent("4_DOCUMENTS");
+        let mut templates = BriefTemplates::builtin();
+        templates.merge(
+            BriefTemplates::from_json(
+                r#"{ "4_DOCUMENTS": { "task_definition": "Write {label} for {package_id} ({unknown}" } }"#,
+            )
+            .unwrap(),
+        );
+        let mut deliverable = deliverable();
+        deliverable.label = "{package_id} annex".to_string();
+
+        let brief = BriefBuilder::new(&agent, &templates)
+            .with_references("| GR-12 | Geotech |\n| EN 1997-1 | Eurocode 7 |\n")
+            .build(&deliverable);
+        assert_eq!(brief.task_definition, "Write {package_id} annex for PKG-001 ({unknown}");
+        assert_eq!(brief.inputs["references"], json!(["GR-12", "EN 1997-1"]));
+    }
+
+    #[test]
+    fn unknown_agents_get_generic_template() {
+        let agent = agent("CUSTOM");
+        let brief = BriefBuilder::new(&agent, &BriefTemplates::builtin()).build(&deliverable());
+        assert_eq!(brief.task_definition, "Run CUSTOM for DEL-01.01 (Foundation)");
+    }
+}
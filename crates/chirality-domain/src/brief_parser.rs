@@ -2,7 +2,39 @@
 //!
 //! From chirality-app: Task agents receive briefs in a structured format
 //! that defines the task, scope, outputs, constraints, and success criteria.
+//! Briefs arrive either as JSON or as a markdown INIT-TASK block:
+//!
+//! ````text
+//! # INIT-TASK
+//!
+//! ## Task
+//! Generate initial drafts for DEL-01.01
+//!
+//! ## Scope
+//! Single deliverable
+//!
+//! ## Outputs
+//! - Datasheet.md
+//!
+//! ## Constraints
+//! - Mark unknowns as TBD
+//!
+//! ## Success Criteria
+//! - All four documents exist
+//!
+//! ## Inputs
+//! ```json
+//! { "deliverable_id": "DEL-01.01" }
+//! ```
+//! ````
+//!
+//! Task and scope text may contain lines that would otherwise read as
+//! structure; the renderer backslash-escapes lines starting with `#`, a code
+//! fence or a backslash (`\## Notes`), as Markdown does, and the parser drops
+//! the escape. List items are single lines, so rendering a brief whose
+//! outputs, constraints or success criteria contain a newline fails.
 
+use crate::brief_schema::BriefSchemas;
 use crate::entities::SessionBrief;
 use crate::error::DomainError;
 
@@ -45,24 +77,140 @@ impl BriefParser {
         })
     }
 
-    /// Validate a brief against an agent's expected format.
-    pub fn validate(brief: &SessionBrief, agent_name: &str) -> Result<(), DomainError> {
-        // Basic validation
-        if brief.task_definition.is_empty() {
+    /// Parse a markdown INIT-TASK brief.
+    ///
+    /// Section headings are matched case-insensitively (`## Output Contract`
+    /// works as well as `## Outputs`); `inputs` come from a fenced JSON or
+    /// YAML block under `## Inputs`.
+    pub fn parse_markdown(markdown: &str) -> Result<SessionBrief, DomainError> {
+        let mut sections: Vec<(Section, usize, Vec<&str>)> = Vec::new();
+        let mut in_fence = false;
+        for (index, line) in markdown.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("```") {
+                in_fence = !in_fence;
+            } else if !in_fence {
+                if let Some(heading) = trimmed.strip_prefix("## ") {
+                    let section = Section::from_heading(heading).ok_or_else(|| {
+                        DomainError::InvalidBrief {
+                            reason: format!("unknown section {:?} at line {}", heading.trim(), index + 1),
+                        }
+                    })?;
+                    if sections.iter().any(|(s, _, _)| *s == section) {
+                        return Err(DomainError::InvalidBrief {
+                            reason: format!("duplicate section {:?} at line {}", heading.trim(), index + 1),
+                        });
+                    }
+                    sections.push((section, index + 1, Vec::new()));
+                    continue;
+                }
+                if trimmed.starts_with("# ") && sections.is_empty() {
+                    continue;
+                }
+            }
+            if let Some((_, _, body)) = sections.last_mut() {
+                body.push(line);
+            } else if !trimmed.is_empty() {
+                return Err(DomainError::InvalidBrief {
+                    reason: format!("text before the first section at line {}", index + 1),
+                });
+            }
+        }
+
+        let text = |section: Section| {
+            sections
+                .iter()
+                .find(|(s, _, _)| *s == section)
+                .map(|(_, _, body)| {
+                    let lines: Vec<String> = body.iter().map(|line| unescape_line(line)).collect();
+                    lines.join("\n").trim().to_string()
+                })
+                .unwrap_or_default()
+        };
+        let list = |section: Section| {
+            sections
+                .iter()
+                .find(|(s, _, _)| *s == section)
+                .map(|(_, _, body)| parse_markdown_list(body))
+                .unwrap_or_default()
+        };
+
+        let task_definition = text(Section::Task);
+        if task_definition.is_empty() {
             return Err(DomainError::InvalidBrief {
-                reason: "task_definition cannot be empty".to_string(),
+                reason: "Missing task_definition".to_string(),
             });
         }
+        let inputs = match sections.iter().find(|(s, _, _)| *s == Section::Inputs) {
+            Some((_, line, body)) => parse_inputs_block(body, *line)?,
+            None => serde_json::Value::Null,
+        };
 
-        // Agent-specific validation
-        match agent_name {
-            "4_DOCUMENTS" => Self::validate_4_documents_brief(brief),
-            "PREPARATION" => Self::validate_preparation_brief(brief),
-            "CHIRALITY_FRAMEWORK" => Self::validate_chirality_framework_brief(brief),
-            "DEPENDENCIES" => Self::validate_dependencies_brief(brief),
-            "AGGREGATION" => Self::validate_aggregation_brief(brief),
-            _ => Ok(()), // Unknown agents get no additional validation
+        Ok(SessionBrief {
+            task_definition,
+            scope_description: text(Section::Scope),
+            output_contract: list(Section::Outputs),
+            constraints: list(Section::Constraints),
+            success_criteria: list(Section::SuccessCriteria),
+            inputs,
+        })
+    }
+
+    /// Render a brief as the canonical INIT-TASK markdown used in prompts.
+    ///
+    /// Fails if a list item contains a newline, since it could not be read
+    /// back as the same item.
+    pub fn render_markdown(brief: &SessionBrief) -> Result<String, DomainError> {
+        let mut out = String::from("# INIT-TASK\n");
+        let mut section = |heading: &str, body: &str| {
+            if !body.is_empty() {
+                out.push_str(&format!("\n## {}\n{}\n", heading, body));
+            }
+        };
+        let bullets = |field: &str, items: &[String]| {
+            items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| match item.contains(['\n', '\r']) {
+                    true => Err(DomainError::InvalidBrief {
+                        reason: format!("{}[{}] spans several lines; list items must be one line", field, index),
+                    }),
+                    false => Ok(format!("- {}\n", item)),
+                })
+                .collect::<Result<String, _>>()
+        };
+        let text = |body: &str| {
+            body.trim()
+                .lines()
+                .map(|line| escape_line(line) + "\n")
+                .collect::<String>()
+        };
+
+        let output_contract = bullets("output_contract", &brief.output_contract)?;
+        let constraints = bullets("constraints", &brief.constraints)?;
+        let success_criteria = bullets("success_criteria", &brief.success_criteria)?;
+        section(Section::Task.heading(), &text(&brief.task_definition));
+        section(Section::Scope.heading(), &text(&brief.scope_description));
+        section(Section::Outputs.heading(), &output_contract);
+        section(Section::Constraints.heading(), &constraints);
+        section(Section::SuccessCriteria.heading(), &success_criteria);
+        if !brief.inputs.is_null() {
+            let json = serde_json::to_string_pretty(&brief.inputs)
+                .expect("JSON values always serialize");
+            section(Section::Inputs.heading(), &format!("```json\n{}\n```\n", json));
         }
+        Ok(out)
+    }
+
+    /// Validate a brief against an agent's schema, reporting every violation.
+    ///
+    /// Agents without a schema only need a non-empty task definition.
+    pub fn validate(
+        brief: &SessionBrief,
+        agent_name: &str,
+        schemas: &BriefSchemas,
+    ) -> Result<(), DomainError> {
+        schemas.validate(agent_name, brief)
     }
 
     fn parse_string_array(value: Option<&serde_json::Value>) -> Vec<String> {
@@ -75,61 +223,120 @@ impl BriefParser {
             })
             .unwrap_or_default()
     }
+}
 
-    fn validate_4_documents_brief(brief: &SessionBrief) -> Result<(), DomainError> {
-        // 4_DOCUMENTS needs deliverable context
-        if brief.inputs.get("deliverable_id").is_none() {
-            return Err(DomainError::InvalidBrief {
-                reason: "4_DOCUMENTS requires deliverable_id in inputs".to_string(),
-            });
+/// Sections of a markdown INIT-TASK brief.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Task,
+    Scope,
+    Outputs,
+    Constraints,
+    SuccessCriteria,
+    Inputs,
+}
+
+impl Section {
+    fn from_heading(heading: &str) -> Option<Self> {
+        match heading.trim().trim_end_matches(':').to_ascii_lowercase().as_str() {
+            "task" | "task definition" => Some(Section::Task),
+            "scope" | "scope description" => Some(Section::Scope),
+            "outputs" | "output contract" => Some(Section::Outputs),
+            "constraints" => Some(Section::Constraints),
+            "success criteria" => Some(Section::SuccessCriteria),
+            "inputs" => Some(Section::Inputs),
+            _ => None,
         }
-        Ok(())
     }
 
-    fn validate_preparation_brief(brief: &SessionBrief) -> Result<(), DomainError> {
-        // PREPARATION needs package or project context
-        if brief.inputs.get("package_id").is_none()
-            && brief.inputs.get("project_id").is_none()
-        {
-            return Err(DomainError::InvalidBrief {
-                reason: "PREPARATION requires package_id or project_id in inputs".to_string(),
-            });
+    /// Canonical heading used by the renderer.
+    fn heading(&self) -> &'static str {
+        match self {
+            Section::Task => "Task",
+            Section::Scope => "Scope",
+            Section::Outputs => "Outputs",
+            Section::Constraints => "Constraints",
+            Section::SuccessCriteria => "Success Criteria",
+            Section::Inputs => "Inputs",
         }
-        Ok(())
     }
+}
 
-    fn validate_chirality_framework_brief(brief: &SessionBrief) -> Result<(), DomainError> {
-        // CHIRALITY_FRAMEWORK needs deliverable context
-        if brief.inputs.get("deliverable_id").is_none() {
-            return Err(DomainError::InvalidBrief {
-                reason: "CHIRALITY_FRAMEWORK requires deliverable_id in inputs".to_string(),
+/// Bullet (`-`, `*`) or numbered items; indented lines continue the item above.
+fn parse_markdown_list(lines: &[&str]) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        // A bare marker (`-` once trimmed) is an empty item, not a continuation.
+        let item = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| ["-", "*"].contains(&trimmed).then_some(""))
+            .or_else(|| {
+                let (number, rest) = trimmed.split_once(". ")?;
+                number.chars().all(|c| c.is_ascii_digit()).then_some(rest)
             });
+        match (item, items.last_mut()) {
+            (Some(item), _) => items.push(item.trim().to_string()),
+            (None, Some(last)) => {
+                last.push(' ');
+                last.push_str(trimmed);
+            }
+            (None, None) => items.push(trimmed.to_string()),
         }
-        Ok(())
     }
+    items
+}
+
+/// Prefix a backslash to a text line that would read as a heading or fence.
+fn escape_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if ["#", "```", "\\"].iter().any(|marker| trimmed.starts_with(marker)) {
+        let indent = &line[..line.len() - trimmed.len()];
+        format!("{}\\{}", indent, trimmed)
+    } else {
+        line.to_string()
+    }
+}
 
-    fn validate_dependencies_brief(brief: &SessionBrief) -> Result<(), DomainError> {
-        // DEPENDENCIES needs scope (deliverable, package, or project)
-        if brief.inputs.get("deliverable_id").is_none()
-            && brief.inputs.get("package_id").is_none()
-            && brief.inputs.get("project_id").is_none()
-        {
-            return Err(DomainError::InvalidBrief {
-                reason: "DEPENDENCIES requires a scope (deliverable_id, package_id, or project_id)"
-                    .to_string(),
-            });
+/// Undo [`escape_line`].
+fn unescape_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    match trimmed.strip_prefix('\\') {
+        Some(rest) if ["#", "```", "\\"].iter().any(|marker| rest.starts_with(marker)) => {
+            let indent = &line[..line.len() - trimmed.len()];
+            format!("{}{}", indent, rest)
         }
-        Ok(())
+        _ => line.to_string(),
     }
+}
 
-    fn validate_aggregation_brief(brief: &SessionBrief) -> Result<(), DomainError> {
-        // AGGREGATION needs project context and output path
-        if brief.inputs.get("project_id").is_none() {
-            return Err(DomainError::InvalidBrief {
-                reason: "AGGREGATION requires project_id in inputs".to_string(),
-            });
+/// The fenced block under `## Inputs`, as JSON or YAML.
+fn parse_inputs_block(lines: &[&str], heading_line: usize) -> Result<serde_json::Value, DomainError> {
+    let error = |reason: String| DomainError::InvalidBrief {
+        reason: format!("inputs (section at line {}): {}", heading_line, reason),
+    };
+    let mut body = lines.iter().map(|l| l.trim_end()).skip_while(|l| l.trim().is_empty());
+    let fence = match body.next() {
+        None => return Ok(serde_json::Value::Null),
+        Some(fence) if fence.trim_start().starts_with("```") => fence.trim_start(),
+        Some(_) => return Err(error("expected a fenced ```json or ```yaml block".to_string())),
+    };
+    let language = fence.trim_start_matches('`').trim().to_ascii_lowercase();
+    let content: Vec<&str> = body.take_while(|l| !l.trim_start().starts_with("```")).collect();
+    let content = content.join("\n");
+    match language.as_str() {
+        "json" => serde_json::from_str(&content).map_err(|e| error(format!("invalid JSON: {}", e))),
+        "yaml" | "yml" => {
+            serde_yaml_ng::from_str(&content).map_err(|e| error(format!("invalid YAML: {}", e)))
         }
-        Ok(())
+        "" => serde_json::from_str(&content)
+            .or_else(|_| serde_yaml_ng::from_str(&content))
+            .map_err(|e| error(format!("neither JSON nor YAML: {}", e))),
+        other => Err(error(format!("unsupported block language {:?}", other))),
     }
 }
 
@@ -167,7 +374,104 @@ mod tests {
             inputs: json!({}),
         };
 
-        let result = BriefParser::validate(&brief, "4_DOCUMENTS");
+        let result = BriefParser::validate(&brief, "4_DOCUMENTS", &BriefSchemas::builtin());
         assert!(result.is_err());
     }
+
+    fn same(a: &SessionBrief, b: &SessionBrief) -> bool {
+        serde_json::to_value(a).unwrap() == serde_json::to_value(b).unwrap()
+    }
+
+    #[test]
+    fn parses_human_markdown_with_json_inputs() {
+        let markdown = "# INIT-TASK\n\n\
+            ## Task Definition\nGenerate initial drafts\nfor DEL-01.01\n\n\
+            ## Output Contract\n1. Datasheet.md\n2. Specification.md\n\n\
+            ## Constraints\n* Use existing references only\n* Mark unknowns\n  as TBD\n\n\
+            ## Inputs\n```\n{ \"deliverable_id\": \"DEL-01.01\", \"revision\": 2 }\n```\n";
+
+        let brief = BriefParser::parse_markdown(markdown).unwrap();
+        assert_eq!(brief.task_definition, "Generate initial drafts\nfor DEL-01.01");
+        assert_eq!(brief.output_contract, ["Datasheet.md", "Specification.md"]);
+        assert_eq!(brief.constraints[1], "Mark unknowns as TBD");
+        assert!(brief.success_criteria.is_empty());
+        assert_eq!(brief.inputs, json!({ "deliverable_id": "DEL-01.01", "revision": 2 }));
+
+        let rendered = BriefParser::render_markdown(&brief).unwrap();
+        assert!(rendered.contains("## Outputs\n- Datasheet.md\n- Specification.md\n"));
+        assert!(same(&BriefParser::parse_markdown(&rendered).unwrap(), &brief));
+    }
+
+    #[test]
+    fn render_round_trips() {
+        let brief = BriefParser::parse(&json!({
+            "task_definition": "Aggregate project status",
+            "scope_description": "Whole project",
+            "output_contract": ["summary.md"],
+            "constraints": [],
+            "success_criteria": ["Every package listed"],
+            "inputs": { "project_id": "proj:1", "packages": ["PKG-001"] }
+        }))
+        .unwrap();
+
+        let rendered = BriefParser::render_markdown(&brief).unwrap();
+        assert!(rendered.starts_with("# INIT-TASK\n\n## Task\nAggregate project status\n"));
+        assert!(!rendered.contains("## Constraints"));
+        assert!(same(&BriefParser::parse_markdown(&rendered).unwrap(), &brief));
+    }
+
+    #[test]
+    fn markdown_errors_name_the_problem() {
+        assert!(matches!(
+            BriefParser::parse_markdown("## Scope\nNo task here\n"),
+            Err(DomainError::InvalidBrief { ref reason }) if reason == "Missing task_definition"
+        ));
+        assert!(matches!(
+            BriefParser::parse_markdown("## Task\nx\n## Budget\n"),
+            Err(DomainError::InvalidBrief { ref reason }) if reason.contains("line 3")
+        ));
+        assert!(BriefParser::parse_markdown("## Task\nx\n## Inputs\n```json\n{ oops\n```\n").is_err());
+        assert!(BriefParser::parse_markdown("## Task\nx\n## Inputs\n```yaml\nrevision: [2\n```\n").is_err());
+        assert!(matches!(
+            BriefParser::parse_markdown("## Task\nx\n## Inputs\n```toml\nrevision = 2\n```\n"),
+            Err(DomainError::InvalidBrief { ref reason }) if reason.contains("\"toml\"")
+        ));
+    }
+
+    #[test]
+    fn parses_yaml_inputs_and_keeps_empty_items() {
+        let markdown = "## Task\nGenerate drafts\n\n\
+            ## Constraints\n- Mark unknowns\n- \n- Cite sources\n\n\
+            ## Inputs\n```yaml\ndeliverable_id: DEL-01.01\nrevision: 2\npackages:\n  - PKG-001\n```\n";
+
+        let brief = BriefParser::parse_markdown(markdown).unwrap();
+        assert_eq!(
+            brief.inputs,
+            json!({ "deliverable_id": "DEL-01.01", "revision": 2, "packages": ["PKG-001"] })
+        );
+        assert_eq!(brief.constraints, ["Mark unknowns", "", "Cite sources"]);
+
+        let rendered = BriefParser::render_markdown(&brief).unwrap();
+        assert!(same(&BriefParser::parse_markdown(&rendered).unwrap(), &brief));
+    }
+
+    #[test]
+    fn structure_in_text_and_items_survives_or_is_refused() {
+        let mut brief = BriefParser::parse(&json!({
+            "task_definition": "Summarise the notes:\n## Notes\n```\n\\## literal\n# Title",
+            "scope_description": "One package\n## Outputs",
+            "output_contract": ["## not a heading", "```"],
+        }))
+        .unwrap();
+
+        let rendered = BriefParser::render_markdown(&brief).unwrap();
+        assert!(rendered.contains("\n\\## Notes\n\\```\n\\\\## literal\n\\# Title\n"));
+        assert!(same(&BriefParser::parse_markdown(&rendered).unwrap(), &brief));
+
+        brief.constraints = vec!["fine".to_string(), "two\nlines".to_string()];
+        assert!(matches!(
+            BriefParser::render_markdown(&brief),
+            Err(DomainError::InvalidBrief { ref reason }) if reason.starts_with("constraints[1]")
+        ));
+    }
 }
@@ -0,0 +1,378 @@
+//! Declarative per-agent brief schemas.
+//!
+//! A schema lists the `inputs` keys an agent expects (with types and allowed
+//! values) and how many `output_contract` entries a brief must name.
+//! Schemas come from `AGENT_*.md` headers or a JSON config file:
+//!
+//! ```json
+//! {
+//!   "4_DOCUMENTS": {
+//!     "inputs": [
+//!       { "keys": ["deliverable_id"], "required": true, "type": "string" },
+//!       { "keys": ["mode"], "allowed_values": ["draft", "revise"] }
+//!     ],
+//!     "min_output_contract": 4
+//!   }
+//! }
+//! ```
+//!
+//! Validation reports every violation, not just the first.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::entities::SessionBrief;
+use crate::error::DomainError;
+
+/// Schemas for the agents shipped with chirality-app.
+const BUILTIN_SCHEMAS: &str = include_str!("brief_schemas.json");
+
+/// What a brief for one agent must contain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BriefSchema {
+    #[serde(default)]
+    pub inputs: Vec<InputRule>,
+    #[serde(default)]
+    pub min_output_contract: usize,
+    /// Accept `inputs` keys no rule mentions.
+    #[serde(default = "default_true")]
+    pub allow_unknown_inputs: bool,
+}
+
+impl Default for BriefSchema {
+    fn default() -> Self {
+        Self {
+            inputs: Vec::new(),
+            min_output_contract: 0,
+            allow_unknown_inputs: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// One expected `inputs` entry, satisfied by any one of `keys`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputRule {
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, rename = "type")]
+    pub value_type: InputType,
+    /// If non-empty, the value must be one of these.
+    #[serde(default)]
+    pub allowed_values: Vec<Value>,
+}
+
+impl InputRule {
+    pub fn required(keys: &[&str]) -> Self {
+        Self {
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+            required: true,
+            value_type: InputType::Any,
+            allowed_values: Vec::new(),
+        }
+    }
+
+    pub fn optional(keys: &[&str]) -> Self {
+        Self {
+            required: false,
+            ..Self::required(keys)
+        }
+    }
+
+    pub fn with_type(mut self, value_type: InputType) -> Self {
+        self.value_type = value_type;
+        self
+    }
+}
+
+/// JSON type expected for an input value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputType {
+    #[default]
+    Any,
+    String,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+impl InputType {
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            InputType::Any => true,
+            InputType::String => value.is_string(),
+            InputType::Number => value.is_number(),
+            InputType::Boolean => value.is_boolean(),
+            InputType::Array => value.is_array(),
+            InputType::Object => value.is_object(),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InputType::Any => "any",
+            InputType::String => "string",
+            InputType::Number => "number",
+            InputType::Boolean => "boolean",
+            InputType::Array => "array",
+            InputType::Object => "object",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "any" => Some(InputType::Any),
+            "string" => Some(InputType::String),
+            "number" => Some(InputType::Number),
+            "boolean" | "bool" => Some(InputType::Boolean),
+            "array" => Some(InputType::Array),
+            "object" => Some(InputType::Object),
+            _ => None,
+        }
+    }
+}
+
+/// One problem with a brief.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BriefViolation {
+    /// Field the problem is about (e.g. `inputs.deliverable_id`).
+    pub field: String,
+    pub message: String,
+}
+
+impl BriefViolation {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for BriefViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl BriefSchema {
+    /// Every way `brief` falls short of this schema.
+    pub fn violations(&self, brief: &SessionBrief) -> Vec<BriefViolation> {
+        let mut violations = Vec::new();
+        if brief.task_definition.trim().is_empty() {
+            violations.push(BriefViolation::new("task_definition", "cannot be empty"));
+        }
+        if brief.output_contract.len() < self.min_output_contract {
+            violations.push(BriefViolation::new(
+                "output_contract",
+                format!(
+                    "needs at least {} entries, found {}",
+                    self.min_output_contract,
+                    brief.output_contract.len()
+                ),
+            ));
+        }
+
+        let empty = serde_json::Map::new();
+        let inputs = match &brief.inputs {
+            Value::Object(map) => map,
+            Value::Null => &empty,
+            other => {
+                violations.push(BriefViolation::new(
+                    "inputs",
+                    format!("must be an object, found {}", json_type(other)),
+                ));
+                &empty
+            }
+        };
+
+        for rule in &self.inputs {
+            let present = rule
+                .keys
+                .iter()
+                .find_map(|k| inputs.get(k).map(|v| (k, v)));
+            let Some((key, value)) = present else {
+                if rule.required {
+                    violations.push(BriefViolation::new(
+                        format!("inputs.{}", rule.keys.join("|")),
+                        match rule.keys.as_slice() {
+                            [_] => "is required".to_string(),
+                            _ => format!("one of {} is required", rule.keys.join(", ")),
+                        },
+                    ));
+                }
+                continue;
+            };
+            let field = format!("inputs.{}", key);
+            if !rule.value_type.matches(value) {
+                violations.push(BriefViolation::new(
+                    field,
+                    format!("must be {}, found {}", rule.value_type.as_str(), json_type(value)),
+                ));
+            } else if !rule.allowed_values.is_empty() && !rule.allowed_values.contains(value) {
+                let allowed: Vec<String> = rule.allowed_values.iter().map(Value::to_string).collect();
+                violations.push(BriefViolation::new(
+                    field,
+                    format!("{} is not one of {}", value, allowed.join(", ")),
+                ));
+            }
+        }
+
+        if !self.allow_unknown_inputs {
+            for key in inputs.keys() {
+                if !self.inputs.iter().any(|r| r.keys.contains(key)) {
+                    violations.push(BriefViolation::new(
+                        format!("inputs.{}", key),
+                        "is not expected by this agent",
+                    ));
+                }
+            }
+        }
+        violations
+    }
+
+    /// Validate a brief, failing with every violation found.
+    pub fn validate(&self, brief: &SessionBrief) -> Result<(), DomainError> {
+        let violations = self.violations(brief);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(DomainError::BriefViolations { violations })
+        }
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Brief schemas keyed by agent name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BriefSchemas {
+    schemas: BTreeMap<String, BriefSchema>,
+}
+
+impl BriefSchemas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schemas for the agents shipped with chirality-app.
+    pub fn builtin() -> Self {
+        Self::from_json(BUILTIN_SCHEMAS).expect("built-in brief schemas are valid")
+    }
+
+    /// Parse a JSON config file mapping agent names to schemas.
+    pub fn from_json(content: &str) -> Result<Self, DomainError> {
+        serde_json::from_str(content).map_err(|e| DomainError::InvalidBrief {
+            reason: format!("invalid brief schema config: {}", e),
+        })
+    }
+
+    /// Add or replace the schema for an agent.
+    pub fn insert(&mut self, agent_name: impl Into<String>, schema: BriefSchema) {
+        self.schemas.insert(agent_name.into(), schema);
+    }
+
+    /// Overlay `other` on top of these schemas, replacing per agent.
+    pub fn merge(&mut self, other: BriefSchemas) {
+        self.schemas.extend(other.schemas);
+    }
+
+    pub fn get(&self, agent_name: &str) -> Option<&BriefSchema> {
+        self.schemas.get(agent_name)
+    }
+
+    /// Validate a brief for an agent; agents without a schema get the
+    /// default (a non-empty task definition).
+    pub fn validate(&self, agent_name: &str, brief: &SessionBrief) -> Result<(), DomainError> {
+        match self.get(agent_name) {
+            Some(schema) => schema.validate(brief),
+            None => BriefSchema::default().validate(brief),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn brief(inputs: Value, outputs: usize) -> SessionBrief {
+        SessionBrief {
+            task_definition: "Generate docs".to_string(),
+            scope_description: String::new(),
+            output_contract: (0..outputs).map(|i| format!("out{}.md", i)).collect(),
+            constraints: vec![],
+            success_criteria: vec![],
+            inputs,
+        }
+    }
+
+    #[test]
+    fn reports_every_violation_at_once() {
+        let schemas = BriefSchemas::from_json(
+            r#"{ "X": {
+                "inputs": [
+                    { "keys": ["deliverable_id"], "required": true, "type": "string" },
+                    { "keys": ["package_id", "project_id"], "required": true },
+                    { "keys": ["mode"], "allowed_values": ["draft", "revise"] }
+                ],
+                "min_output_contract": 2,
+                "allow_unknown_inputs": false
+            } }"#,
+        )
+        .unwrap();
+        let brief = SessionBrief {
+            task_definition: " ".to_string(),
+            ..brief(json!({ "deliverable_id": 7, "mode": "rewrite", "extra": true }), 1)
+        };
+
+        let violations = schemas.get("X").unwrap().violations(&brief);
+        let fields: Vec<&str> = violations.iter().map(|v| v.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "task_definition",
+                "output_contract",
+                "inputs.deliverable_id",
+                "inputs.package_id|project_id",
+                "inputs.mode",
+                "inputs.extra",
+            ]
+        );
+        assert!(matches!(
+            schemas.validate("X", &brief),
+            Err(DomainError::BriefViolations { ref violations }) if violations.len() == 6
+        ));
+    }
+
+    #[test]
+    fn builtin_schemas_cover_shipped_agents() {
+        let schemas = BriefSchemas::builtin();
+        assert!(schemas.validate("4_DOCUMENTS", &brief(json!({}), 0)).is_err());
+        assert!(schemas
+            .validate("4_DOCUMENTS", &brief(json!({ "deliverable_id": "DEL-01.01" }), 0))
+            .is_ok());
+        assert!(schemas
+            .validate("PREPARATION", &brief(json!({ "project_id": "p" }), 0))
+            .is_ok());
+        assert!(schemas.validate("SOMETHING_NEW", &brief(Value::Null, 0)).is_ok());
+    }
+}
@@ -0,0 +1,451 @@
+//! Decomposition document parser.
+//!
+//! From chirality-app: the decomposition breaks a project's scope into
+//! packages and deliverables, and the workspace tree mirrors it. The document
+//! is markdown with one `##` heading per package and one `###` heading per
+//! deliverable:
+//!
+//! ```text
+//! # Decomposition
+//!
+//! ## PKG-001 Civil Works
+//! - Site preparation
+//! - Foundations
+//!
+//! ### DEL-01.01 Foundation Design
+//! - Type: Design Report
+//! - Discipline: Civil
+//! - Responsible: Acme Engineering
+//! - Artifacts: calcs/loads.xlsx, drawings/layout.dwg
+//! ```
+//!
+//! Bullets under a package heading are its scope items; bullets under a
+//! deliverable heading are `Key: value` attributes. `Artifacts:` may also be
+//! followed by an indented list.
+//!
+//! An optional `## Scope of Work` (or `## SOW`) section lists the project's
+//! scope items; each must be covered by exactly one package (see
+//! [`ScopePartition`]).
+
+use std::path::{Path, PathBuf};
+
+use crate::entities::{Deliverable, DeliverableId, Package, PackageId, Project};
+use crate::error::DomainError;
+use crate::folder_names::{FolderName, PackageFolder};
+use crate::scope_partition::{PartitionIssue, ScopePartition};
+
+/// Packages and deliverables declared by a decomposition document.
+#[derive(Debug, Clone)]
+pub struct Decomposition {
+    pub packages: Vec<Package>,
+    pub deliverables: Vec<Deliverable>,
+    /// Items from the `Scope of Work` section, empty if there is none.
+    pub sow_items: Vec<String>,
+}
+
+/// A difference between the decomposition and the folder tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// Declared in the decomposition but has no folder.
+    MissingPackage { package_id: PackageId, path: PathBuf },
+    MissingDeliverable {
+        deliverable_id: DeliverableId,
+        path: PathBuf,
+    },
+    /// Has a folder but is not in the decomposition.
+    UndeclaredPackage { package_id: PackageId },
+    UndeclaredDeliverable {
+        deliverable_id: DeliverableId,
+        path: PathBuf,
+    },
+    /// Folder sits under a different package than the decomposition says.
+    MisplacedDeliverable {
+        deliverable_id: DeliverableId,
+        expected: PackageId,
+        found: PackageId,
+    },
+    /// Folder label differs from the decomposition's.
+    LabelMismatch {
+        id: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl Decomposition {
+    /// Parse a decomposition document for `project`.
+    ///
+    /// Folder paths are derived from `project.workspace_path`, following the
+    /// `PKG-###_Label/DEL-##.##_Label` naming convention.
+    pub fn parse(project: &Project, content: &str) -> Result<Self, DomainError> {
+        let mut decomposition = Decomposition {
+            packages: Vec::new(),
+            deliverables: Vec::new(),
+            sow_items: Vec::new(),
+        };
+        // Inside a `###` section (attributes) rather than a `##` one (scope items).
+        let mut in_deliverable = false;
+        let mut in_artifacts = false;
+        let mut in_sow = false;
+
+        for (index, raw) in content.lines().enumerate() {
+            let line = index + 1;
+            let error = |reason: String| DomainError::InvalidDecomposition { line, reason };
+            let trimmed = raw.trim();
+
+            if let Some(heading) = trimmed.strip_prefix("### ") {
+                in_deliverable = true;
+                in_artifacts = false;
+                in_sow = false;
+                let package = decomposition.packages.last().ok_or_else(|| {
+                    error("deliverable heading before any package heading".to_string())
+                })?;
+                let (id_part, label) = split_heading(heading);
+                let folder = match FolderName::parse(id_part) {
+                    FolderName::Deliverable(folder) => folder,
+                    _ => return Err(error(format!("expected DEL-##.## <Label>, found {:?}", heading))),
+                };
+                if let (Some(expected), Some(found)) = (package_number(&package.folder_name), folder.package_number) {
+                    if expected != found {
+                        return Err(error(format!("{} is listed under {}", folder.id, package.id)));
+                    }
+                }
+                if decomposition.deliverables.iter().any(|d| d.id == folder.id) {
+                    return Err(error(format!("duplicate deliverable {}", folder.id)));
+                }
+                let label = heading_label(label, folder.label.as_deref(), folder.id.as_str());
+                let path = project
+                    .workspace_path
+                    .join(&package.folder_name)
+                    .join(folder_name(folder.id.as_str(), &label));
+                let mut deliverable = Deliverable::new(package.id.clone(), label, path)
+                    .with_revision_scheme(project.revision_scheme);
+                deliverable.id = folder.id;
+                decomposition.deliverables.push(deliverable);
+                continue;
+            }
+            if let Some(heading) = trimmed.strip_prefix("## ") {
+                in_deliverable = false;
+                in_artifacts = false;
+                in_sow = is_sow_heading(heading);
+                if in_sow {
+                    continue;
+                }
+                let (id_part, label) = split_heading(heading);
+                let folder = match FolderName::parse(id_part) {
+                    FolderName::Package(folder) => folder,
+                    _ => return Err(error(format!("expected PKG-### <Label>, found {:?}", heading))),
+                };
+                if decomposition.packages.iter().any(|p| p.id == folder.id) {
+                    return Err(error(format!("duplicate package {}", folder.id)));
+                }
+                decomposition.packages.push(build_package(project, folder, label));
+                continue;
+            }
+            if trimmed.starts_with('#') || trimmed.is_empty() {
+                continue;
+            }
+            let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) else {
+                continue; // prose
+            };
+            let item = item.trim();
+
+            if in_sow {
+                decomposition.sow_items.push(item.to_string());
+                continue;
+            }
+            if !in_deliverable {
+                let package = decomposition.packages.last_mut().ok_or_else(|| {
+                    error("list item before any package heading".to_string())
+                })?;
+                package.scope_items.push(item.to_string());
+                continue;
+            }
+            let deliverable = decomposition
+                .deliverables
+                .last_mut()
+                .expect("a deliverable heading opened this section");
+
+            if in_artifacts && raw.starts_with([' ', '\t']) {
+                deliverable.anticipated_artifacts.push(item.to_string());
+                continue;
+            }
+            in_artifacts = false;
+            let (key, value) = item
+                .split_once(':')
+                .ok_or_else(|| error(format!("expected `Key: value`, found {:?}", item)))?;
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "type" => deliverable.deliverable_type = Some(value.to_string()),
+                "discipline" => deliverable.discipline = Some(value.to_string()),
+                "responsible" | "responsible party" => {
+                    deliverable.responsible_party = Some(value.to_string())
+                }
+                "artifacts" | "anticipated artifacts" => {
+                    in_artifacts = value.is_empty();
+                    deliverable.anticipated_artifacts.extend(
+                        value.split(',').map(str::trim).filter(|a| !a.is_empty()).map(String::from),
+                    );
+                }
+                other => return Err(error(format!("unknown deliverable attribute {:?}", other))),
+            }
+        }
+        Ok(decomposition)
+    }
+
+    /// Ways the declared packages fail to partition the scope of work.
+    pub fn partition_issues(&self) -> Vec<PartitionIssue> {
+        ScopePartition::issues(&self.packages, &self.sow_items)
+    }
+
+    /// Compare against the packages and deliverables found in the workspace.
+    ///
+    /// Labels are compared in folder-name form, so `Site Grading` matches a
+    /// `DEL-01.02_Site_Grading` folder. A missing deliverable whose package
+    /// already exists is placed in that package's folder, even if the folder
+    /// is labelled differently from the decomposition.
+    pub fn drift(&self, packages: &[Package], deliverables: &[Deliverable], root: &Path) -> Vec<Drift> {
+        let mut drift = Vec::new();
+        for declared in &self.packages {
+            match packages.iter().find(|p| p.id == declared.id) {
+                None => drift.push(Drift::MissingPackage {
+                    package_id: declared.id.clone(),
+                    path: root.join(&declared.folder_name),
+                }),
+                Some(found) if !same_label(&found.label, &declared.label) => drift.push(Drift::LabelMismatch {
+                    id: declared.id.to_string(),
+                    expected: declared.label.clone(),
+                    found: found.label.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for declared in &self.deliverables {
+            match deliverables.iter().find(|d| d.id == declared.id) {
+                None => drift.push(Drift::MissingDeliverable {
+                    deliverable_id: declared.id.clone(),
+                    path: match (
+                        packages.iter().find(|p| p.id == declared.package_id),
+                        declared.folder_path.file_name(),
+                    ) {
+                        (Some(package), Some(name)) => root.join(&package.folder_name).join(name),
+                        _ => declared.folder_path.clone(),
+                    },
+                }),
+                Some(found) if found.package_id != declared.package_id => {
+                    drift.push(Drift::MisplacedDeliverable {
+                        deliverable_id: declared.id.clone(),
+                        expected: declared.package_id.clone(),
+                        found: found.package_id.clone(),
+                    })
+                }
+                Some(found) if !same_label(&found.label, &declared.label) => drift.push(Drift::LabelMismatch {
+                    id: declared.id.to_string(),
+                    expected: declared.label.clone(),
+                    found: found.label.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for found in packages {
+            if !self.packages.iter().any(|p| p.id == found.id) {
+                drift.push(Drift::UndeclaredPackage {
+                    package_id: found.id.clone(),
+                });
+            }
+        }
+        for found in deliverables {
+            if !self.deliverables.iter().any(|d| d.id == found.id) {
+                drift.push(Drift::UndeclaredDeliverable {
+                    deliverable_id: found.id.clone(),
+                    path: found.folder_path.clone(),
+                });
+            }
+        }
+        drift
+    }
+}
+
+/// Split `DEL-01.01 Foundation Design` into the id and an optional label.
+fn split_heading(heading: &str) -> (&str, Option<String>) {
+    let heading = heading.trim();
+    match heading.split_once(char::is_whitespace) {
+        Some((id, label)) => {
+            let label = label.trim().trim_start_matches(['-', ':', '–']).trim();
+            (id, (!label.is_empty()).then(|| label.to_string()))
+        }
+        None => (heading, None),
+    }
+}
+
+fn is_sow_heading(heading: &str) -> bool {
+    let heading = heading.trim().to_ascii_lowercase();
+    heading == "sow" || heading == "scope of work"
+}
+
+fn build_package(project: &Project, folder: PackageFolder, label: Option<String>) -> Package {
+    let label = heading_label(label, folder.label.as_deref(), folder.id.as_str());
+    Package {
+        folder_name: folder_name(folder.id.as_str(), &label),
+        id: folder.id,
+        project_id: project.id.clone(),
+        label,
+        scope_items: Vec::new(),
+    }
+}
+
+/// Label for a heading: the text after the id, else the label carried by a
+/// folder-form id (`PKG-001_Civil_Works` → `Civil Works`), else the id itself.
+fn heading_label(label: Option<String>, folder_label: Option<&str>, id: &str) -> String {
+    label
+        .or_else(|| folder_label.map(|l| l.replace('_', " ")))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// `PKG-001` + `Civil Works` → `PKG-001_Civil_Works`.
+fn folder_name(id: &str, label: &str) -> String {
+    format!("{}_{}", id, Package::sanitize_label(label))
+}
+
+fn same_label(a: &str, b: &str) -> bool {
+    Package::sanitize_label(a) == Package::sanitize_label(b)
+}
+
+fn package_number(folder_name: &str) -> Option<u32> {
+    match FolderName::parse(folder_name) {
+        FolderName::Package(folder) => folder.number,
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ActorId;
+
+    const SAMPLE: &str = "# Decomposition\n\nProject scope as agreed.\n\n\
+        ## Scope of Work\n\
+        - Site preparation\n\
+        - Foundations\n\
+        - HVAC\n\n\
+        ## PKG-001 Civil Works\n\
+        - Site preparation\n\
+        - Foundations\n\n\
+        ### DEL-01.01 Foundation Design\n\
+        - Type: Design Report\n\
+        - Discipline: Civil\n\
+        - Responsible: Acme Engineering\n\
+        - Artifacts: calcs/loads.xlsx, drawings/layout.dwg\n\n\
+        ### DEL-01.02 Site Grading\n\
+        - Artifacts:\n  - grading.dwg\n  - volumes.xlsx\n\n\
+        ## PKG-002 Mechanical\n";
+
+    fn project() -> Project {
+        Project::new("proj", PathBuf::from("/proj"), ActorId::system())
+    }
+
+    #[test]
+    fn parses_packages_and_deliverables() {
+        let decomposition = Decomposition::parse(&project(), SAMPLE).unwrap();
+
+        assert_eq!(decomposition.packages.len(), 2);
+        let civil = &decomposition.packages[0];
+        assert_eq!(civil.folder_name, "PKG-001_Civil_Works");
+        assert_eq!(civil.scope_items, ["Site preparation", "Foundations"]);
+        assert_eq!(decomposition.sow_items, ["Site preparation", "Foundations", "HVAC"]);
+        assert_eq!(
+            decomposition.partition_issues(),
+            [
+                PartitionIssue::EmptyPackage {
+                    package_id: PackageId::from_legacy(2)
+                },
+                PartitionIssue::UnassignedScopeItem {
+                    item: "HVAC".to_string()
+                },
+            ]
+        );
+
+        let foundation = &decomposition.deliverables[0];
+        assert_eq!(foundation.id, DeliverableId::from_legacy(1, 1));
+        assert_eq!(foundation.package_id, civil.id);
+        assert_eq!(
+            foundation.folder_path,
+            PathBuf::from("/proj/PKG-001_Civil_Works/DEL-01.01_Foundation_Design")
+        );
+        assert_eq!(foundation.deliverable_type.as_deref(), Some("Design Report"));
+        assert_eq!(foundation.responsible_party.as_deref(), Some("Acme Engineering"));
+        assert_eq!(foundation.anticipated_artifacts, ["calcs/loads.xlsx", "drawings/layout.dwg"]);
+        assert_eq!(decomposition.deliverables[1].anticipated_artifacts, ["grading.dwg", "volumes.xlsx"]);
+    }
+
+    #[test]
+    fn folder_form_headings_keep_their_label() {
+        let decomposition = Decomposition::parse(
+            &project(),
+            "## PKG-001_Civil_Works\n### DEL-01.01_Foundation_Design\n",
+        )
+        .unwrap();
+
+        let civil = &decomposition.packages[0];
+        assert_eq!(civil.label, "Civil Works");
+        assert_eq!(civil.folder_name, "PKG-001_Civil_Works");
+        let foundation = &decomposition.deliverables[0];
+        assert_eq!(foundation.label, "Foundation Design");
+        assert_eq!(
+            foundation.folder_path,
+            PathBuf::from("/proj/PKG-001_Civil_Works/DEL-01.01_Foundation_Design")
+        );
+    }
+
+    #[test]
+    fn rejects_structural_errors_with_line() {
+        let cases = [
+            ("### DEL-01.01 Orphan\n", 1),
+            ("## PKG-001 Civil\n### DEL-02.01 Wrong package\n", 2),
+            ("## PKG-001 Civil\n### DEL-01.01 A\n- Colour: red\n", 3),
+            ("## PKG-001 Civil\n## PKG-001 Again\n", 2),
+            ("## Civil Works\n", 1),
+        ];
+        for (content, expected) in cases {
+            match Decomposition::parse(&project(), content) {
+                Err(DomainError::InvalidDecomposition { line, .. }) => assert_eq!(line, expected, "{}", content),
+                other => panic!("expected an error for {:?}, got {:?}", content, other),
+            }
+        }
+    }
+
+    #[test]
+    fn reports_drift_against_folder_tree() {
+        let project = project();
+        let decomposition = Decomposition::parse(&project, SAMPLE).unwrap();
+        let civil = decomposition.packages[0].clone();
+        let mut renamed = decomposition.deliverables[0].clone();
+        renamed.label = "Foundations".to_string();
+        let extra = Deliverable::new(civil.id.clone(), "Extra", PathBuf::from("/proj/x"))
+            .with_legacy_id(1, 9);
+
+        let drift = decomposition.drift(&[civil], &[renamed, extra], Path::new("/proj"));
+        assert_eq!(
+            drift,
+            [
+                Drift::MissingPackage {
+                    package_id: PackageId::from_legacy(2),
+                    path: PathBuf::from("/proj/PKG-002_Mechanical"),
+                },
+                Drift::LabelMismatch {
+                    id: "DEL-01.01".to_string(),
+                    expected: "Foundation Design".to_string(),
+                    found: "Foundations".to_string(),
+                },
+                Drift::MissingDeliverable {
+                    deliverable_id: DeliverableId::from_legacy(1, 2),
+                    path: PathBuf::from("/proj/PKG-001_Civil_Works/DEL-01.02_Site_Grading"),
+                },
+                Drift::UndeclaredDeliverable {
+                    deliverable_id: DeliverableId::from_legacy(1, 9),
+                    path: PathBuf::from("/proj/x"),
+                },
+            ]
+        );
+    }
+}
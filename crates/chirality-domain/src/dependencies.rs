@@ -0,0 +1,536 @@
+//! Deliverable dependencies, parsed from `_DEPENDENCIES.md`.
+//!
+//! Every list item or table row in the file is one dependency. A row that
+//! names a deliverable (`DEL-01.02` or `del:<ULID>`) is an edge to that
+//! deliverable; any other row is an external reference (a vendor drawing,
+//! a client decision, ...). Rows ending in a `(soft)`, `(optional)` or
+//! `(informative)` marker do not block; everything else does, including rows
+//! that merely use those words ("Soft soil investigation").
+//!
+//! ```text
+//! - [x] DEL-01.02 Geotechnical report (input)
+//! - [ ] DEL-02.01 Pump selection (soft)
+//! - [ ] Client approval of site layout (PENDING)
+//! ```
+//!
+//! `DependencyGraph` collects the edges of a whole project, orders
+//! deliverables upstream-first, explains what is blocking a deliverable, and
+//! finds the deliverables downstream of a change.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::entities::{Deliverable, DeliverableId};
+use crate::error::DomainError;
+use crate::readiness::DependenciesResolved;
+use crate::state_machines::DeliverableState;
+
+/// What a dependency points at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type", content = "value")]
+pub enum DependencyTarget {
+    Deliverable(DeliverableId),
+    /// Something outside the project tree, described in words.
+    External(String),
+}
+
+/// How strongly the dependent deliverable relies on its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DependencyKind {
+    /// Work cannot be completed until the target is issued.
+    Hard,
+    /// Useful input, but never blocks.
+    Soft,
+}
+
+/// One entry of a `_DEPENDENCIES.md` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dependency {
+    pub target: DependencyTarget,
+    pub kind: DependencyKind,
+    /// The row text, without list markers or table pipes.
+    pub description: String,
+    /// Ticked off or not marked OPEN/PENDING/UNRESOLVED.
+    pub resolved: bool,
+    /// 1-based line in `_DEPENDENCIES.md`.
+    pub line: usize,
+}
+
+impl Dependency {
+    /// Parse the dependencies of `owner` from its `_DEPENDENCIES.md`.
+    ///
+    /// Mentions of `owner` itself are ignored; a row naming several
+    /// deliverables yields one dependency per deliverable.
+    pub fn parse_file(owner: &DeliverableId, content: &str) -> Vec<Dependency> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut dependencies = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            let Some(text) = row_text(trimmed) else {
+                continue;
+            };
+            // Table header: the row right above a `|---|` separator.
+            let next = lines.get(index + 1).map(|l| l.trim()).unwrap_or("");
+            if trimmed.starts_with('|') && is_table_separator(next) {
+                continue;
+            }
+            if text.is_empty() || is_table_separator(trimmed) {
+                continue;
+            }
+
+            let kind = if has_soft_marker(&text) {
+                DependencyKind::Soft
+            } else {
+                DependencyKind::Hard
+            };
+            let resolved = !DependenciesResolved::is_unresolved(trimmed);
+            let dependency = |target| Dependency {
+                target,
+                kind,
+                description: text.clone(),
+                resolved,
+                line: index + 1,
+            };
+
+            let ids = deliverable_ids(&text);
+            if ids.is_empty() {
+                dependencies.push(dependency(DependencyTarget::External(text.clone())));
+            }
+            for id in ids.into_iter().filter(|id| id != owner) {
+                dependencies.push(dependency(DependencyTarget::Deliverable(id)));
+            }
+        }
+        dependencies
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.kind == DependencyKind::Hard
+    }
+}
+
+/// Text of a list item or table row, or `None` for any other line.
+fn row_text(line: &str) -> Option<String> {
+    if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|m| line.strip_prefix(m)) {
+        let item = item.trim_start();
+        let item = ["[ ]", "[x]", "[X]"]
+            .iter()
+            .find_map(|b| item.strip_prefix(b))
+            .unwrap_or(item);
+        return Some(item.trim().to_string());
+    }
+    if line.starts_with('|') {
+        let cells: Vec<&str> = line
+            .trim_matches('|')
+            .split('|')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .collect();
+        return Some(cells.join(" | "));
+    }
+    None
+}
+
+/// Whether the row ends in `(soft)`, `(optional)` or `(informative)`,
+/// possibly followed by other parenthesised markers such as `(PENDING)`.
+fn has_soft_marker(text: &str) -> bool {
+    let mut rest = text.trim_end().trim_end_matches('|').trim_end();
+    while let Some(inner) = rest.strip_suffix(')') {
+        let Some((before, marker)) = inner.rsplit_once('(') else {
+            return false;
+        };
+        if ["soft", "optional", "informative"]
+            .iter()
+            .any(|m| marker.trim().eq_ignore_ascii_case(m))
+        {
+            return true;
+        }
+        rest = before.trim_end();
+    }
+    false
+}
+
+pub(crate) fn is_table_separator(line: &str) -> bool {
+    line.starts_with('|') && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// Deliverable ids mentioned in `text`, in order, legacy ids normalised.
+fn deliverable_ids(text: &str) -> Vec<DeliverableId> {
+    let mut ids = Vec::new();
+    let mut push = |id: DeliverableId| {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    };
+    for word in text.split(|c: char| c.is_whitespace() || matches!(c, '|' | ',' | ';' | '(' | ')' | '[' | ']' | '`')) {
+        let word = word.trim_end_matches(['.', ':']);
+        if let Some(rest) = word.strip_prefix("DEL-") {
+            let numbers = rest.split_once('.').and_then(|(p, d)| {
+                let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+                (digits(p) && digits(d)).then(|| (p.parse().ok(), d.parse().ok()))
+            });
+            if let Some((Some(package), Some(deliverable))) = numbers {
+                push(DeliverableId::from_legacy(package, deliverable));
+            }
+        } else if let Some(ulid) = word.strip_prefix("del:") {
+            if ulid.len() == 26 && ulid.chars().all(|c| c.is_ascii_alphanumeric()) {
+                push(DeliverableId::from_string(word));
+            }
+        }
+    }
+    ids
+}
+
+/// An upstream deliverable that keeps another one from completing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blocker {
+    pub upstream: DeliverableId,
+    /// `None` if the upstream deliverable is not in the graph.
+    pub state: Option<DeliverableState>,
+    pub dependency: Dependency,
+}
+
+impl Blocker {
+    /// e.g. `DEL-01.02 is IN_PROGRESS (line 3: DEL-01.02 Geotech report)`.
+    pub fn explain(&self) -> String {
+        let state = match self.state {
+            Some(state) => format!("is {}", state),
+            None => "does not exist".to_string(),
+        };
+        format!(
+            "{} {} (line {}: {})",
+            self.upstream, state, self.dependency.line, self.dependency.description
+        )
+    }
+}
+
+/// A deliverable affected by a change to one of its upstream deliverables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Impact {
+    pub deliverable: DeliverableId,
+    /// From the changed deliverable down to the direct upstream of `deliverable`.
+    pub chain: Vec<DeliverableId>,
+    /// `Soft` if any link in the chain is soft.
+    pub kind: DependencyKind,
+}
+
+impl Impact {
+    /// The changed deliverable the impact originates from.
+    pub fn origin(&self) -> &DeliverableId {
+        &self.chain[0]
+    }
+
+    /// e.g. `depends on DEL-01.03 -> DEL-01.02, which changed`.
+    pub fn reason(&self) -> String {
+        let chain: Vec<String> = self.chain.iter().rev().map(|id| id.to_string()).collect();
+        let soft = match self.kind {
+            DependencyKind::Soft => " (soft)",
+            DependencyKind::Hard => "",
+        };
+        format!("depends on {}, which changed{}", chain.join(" -> "), soft)
+    }
+}
+
+/// Project-wide dependency graph between deliverables.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    states: BTreeMap<DeliverableId, DeliverableState>,
+    dependencies: BTreeMap<DeliverableId, Vec<Dependency>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a deliverable and the dependencies parsed from its `_DEPENDENCIES.md`.
+    pub fn add(&mut self, deliverable: &Deliverable, dependencies: Vec<Dependency>) {
+        self.states.insert(deliverable.id.clone(), deliverable.state);
+        self.dependencies.insert(deliverable.id.clone(), dependencies);
+    }
+
+    pub fn dependencies_of(&self, id: &DeliverableId) -> &[Dependency] {
+        self.dependencies.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Deliverables that depend on `id`.
+    pub fn dependents_of<'a>(&'a self, id: &'a DeliverableId) -> impl Iterator<Item = &'a DeliverableId> + 'a {
+        self.dependencies
+            .iter()
+            .filter(move |(_, deps)| deps.iter().any(|d| d.target == DependencyTarget::Deliverable(id.clone())))
+            .map(|(from, _)| from)
+    }
+
+    /// Edges pointing at deliverables that are not in the graph.
+    pub fn dangling(&self) -> Vec<(&DeliverableId, &Dependency)> {
+        self.edges()
+            .filter(|(_, _, to)| !self.states.contains_key(to))
+            .map(|(from, dep, _)| (from, dep))
+            .collect()
+    }
+
+    /// Deliverables ordered so that every deliverable comes after the ones it
+    /// depends on. Ties are broken by id. Fails if the dependencies form a cycle.
+    pub fn topological_order(&self) -> Result<Vec<DeliverableId>, DomainError> {
+        let mut remaining: BTreeMap<&DeliverableId, BTreeSet<&DeliverableId>> = self
+            .states
+            .keys()
+            .map(|id| (id, BTreeSet::new()))
+            .collect();
+        for (from, _, to) in self.edges() {
+            if self.states.contains_key(to) {
+                remaining.entry(from).or_default().insert(to);
+            }
+        }
+
+        let mut order = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let ready: Vec<&DeliverableId> = remaining
+                .iter()
+                .filter(|(_, upstream)| upstream.is_empty())
+                .map(|(id, _)| *id)
+                .collect();
+            if ready.is_empty() {
+                return Err(DomainError::DependencyCycle {
+                    cycle: self.find_cycle(&remaining),
+                });
+            }
+            for id in ready {
+                remaining.remove(id);
+                for upstream in remaining.values_mut() {
+                    upstream.remove(id);
+                }
+                order.push(id.clone());
+            }
+        }
+        Ok(order)
+    }
+
+    /// Hard upstream deliverables of `id` that are not yet ISSUED.
+    pub fn blocked_by(&self, id: &DeliverableId) -> Vec<Blocker> {
+        self.dependencies_of(id)
+            .iter()
+            .filter(|d| d.is_blocking())
+            .filter_map(|d| match &d.target {
+                DependencyTarget::Deliverable(upstream) => {
+                    let state = self.states.get(upstream).copied();
+                    (state != Some(DeliverableState::Issued)).then(|| Blocker {
+                        upstream: upstream.clone(),
+                        state,
+                        dependency: d.clone(),
+                    })
+                }
+                DependencyTarget::External(_) => None,
+            })
+            .collect()
+    }
+
+    /// Deliverables downstream of `changed`, transitively, nearest first.
+    ///
+    /// Each affected deliverable is reported once, through its shortest chain;
+    /// the changed deliverables themselves are not included.
+    pub fn downstream_of(&self, changed: &[DeliverableId]) -> Vec<Impact> {
+        let mut seen: BTreeSet<&DeliverableId> = changed.iter().collect();
+        let mut queue: VecDeque<(Vec<DeliverableId>, DependencyKind)> = changed
+            .iter()
+            .map(|id| (vec![id.clone()], DependencyKind::Hard))
+            .collect();
+        let mut impacts = Vec::new();
+        while let Some((chain, kind)) = queue.pop_front() {
+            let upstream = chain.last().expect("chains are never empty");
+            for (from, dependency, to) in self.edges() {
+                if to != upstream || !seen.insert(from) {
+                    continue;
+                }
+                let kind = match (kind, dependency.kind) {
+                    (DependencyKind::Hard, DependencyKind::Hard) => DependencyKind::Hard,
+                    _ => DependencyKind::Soft,
+                };
+                impacts.push(Impact {
+                    deliverable: from.clone(),
+                    chain: chain.clone(),
+                    kind,
+                });
+                let mut next = chain.clone();
+                next.push(from.clone());
+                queue.push_back((next, kind));
+            }
+        }
+        impacts
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (&DeliverableId, &Dependency, &DeliverableId)> {
+        self.dependencies.iter().flat_map(|(from, deps)| {
+            deps.iter().filter_map(move |d| match &d.target {
+                DependencyTarget::Deliverable(to) => Some((from, d, to)),
+                DependencyTarget::External(_) => None,
+            })
+        })
+    }
+
+    /// Walk upstream edges among `remaining` until a node repeats.
+    fn find_cycle(&self, remaining: &BTreeMap<&DeliverableId, BTreeSet<&DeliverableId>>) -> Vec<String> {
+        let Some(mut current) = remaining.keys().next().copied() else {
+            return Vec::new();
+        };
+        let mut path: Vec<&DeliverableId> = Vec::new();
+        while !path.contains(&current) {
+            path.push(current);
+            // Every node left has at least one upstream node that is also left.
+            current = remaining[current].iter().next().copied().unwrap_or(current);
+        }
+        let start = path.iter().position(|id| *id == current).unwrap_or(0);
+        let mut cycle: Vec<String> = path[start..].iter().rev().map(|id| id.to_string()).collect();
+        cycle.push(cycle[0].clone());
+        cycle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PackageId;
+    use std::path::PathBuf;
+
+    fn deliverable(package: u32, number: u32, state: DeliverableState) -> Deliverable {
+        let mut deliverable =
+            Deliverable::new(PackageId::from_legacy(package), "D", PathBuf::from("D"))
+                .with_legacy_id(package, number);
+        deliverable.state = state;
+        deliverable
+    }
+
+    fn id(package: u32, number: u32) -> DeliverableId {
+        DeliverableId::from_legacy(package, number)
+    }
+
+    #[test]
+    fn parses_lists_and_tables() {
+        let content = "# Dependencies\n\n\
+            - [x] DEL-01.02 Geotechnical report (input)\n\
+            - [ ] DEL-2.1 Pump selection (soft)\n\
+            - [ ] Client approval of site layout (PENDING)\n\
+            - See DEL-01.01 itself\n\n\
+            | Upstream | Item | Status |\n\
+            |---|---|---|\n\
+            | DEL-03.01, DEL-03.02 | Loads | OPEN |\n";
+
+        let deps = Dependency::parse_file(&id(1, 1), content);
+        let targets: Vec<_> = deps.iter().map(|d| &d.target).collect();
+        assert_eq!(
+            targets,
+            [
+                &DependencyTarget::Deliverable(id(1, 2)),
+                &DependencyTarget::Deliverable(id(2, 1)),
+                &DependencyTarget::External("Client approval of site layout (PENDING)".into()),
+                &DependencyTarget::Deliverable(id(3, 1)),
+                &DependencyTarget::Deliverable(id(3, 2)),
+            ]
+        );
+        assert!(deps[0].resolved && deps[0].is_blocking());
+        assert_eq!(deps[1].kind, DependencyKind::Soft);
+        assert!(!deps[2].resolved);
+        assert_eq!(deps[3].line, 10);
+        assert_eq!(deps[3].description, "DEL-03.01, DEL-03.02 | Loads | OPEN");
+    }
+
+    #[test]
+    fn only_an_explicit_trailing_marker_is_soft() {
+        let content = "- DEL-01.02 Soft soil investigation
+            - DEL-01.03 Optional equipment list review
+            - DEL-01.04 Informative annex (see note)
+            - DEL-01.05 Pump selection (Optional) (PENDING)
+            | DEL-01.06 | Loads (informative) |
+";
+
+        let kinds: Vec<_> = Dependency::parse_file(&id(1, 1), content)
+            .iter()
+            .map(|d| d.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                DependencyKind::Hard,
+                DependencyKind::Hard,
+                DependencyKind::Hard,
+                DependencyKind::Soft,
+                DependencyKind::Soft,
+            ]
+        );
+    }
+
+    #[test]
+    fn orders_upstream_first_and_explains_blockers() {
+        let mut graph = DependencyGraph::new();
+        let a = deliverable(1, 1, DeliverableState::InProgress);
+        let b = deliverable(1, 2, DeliverableState::Issued);
+        let c = deliverable(2, 1, DeliverableState::Checking);
+        graph.add(&a, Dependency::parse_file(&a.id, "- DEL-01.02\n- DEL-02.01\n- DEL-09.09\n"));
+        graph.add(&b, Vec::new());
+        graph.add(&c, Dependency::parse_file(&c.id, "- DEL-01.02\n"));
+
+        assert_eq!(graph.topological_order().unwrap(), [id(1, 2), id(2, 1), id(1, 1)]);
+        assert_eq!(graph.dangling().len(), 1);
+        assert_eq!(graph.dependents_of(&b.id).count(), 2);
+
+        let blockers: Vec<String> = graph.blocked_by(&a.id).iter().map(Blocker::explain).collect();
+        assert_eq!(
+            blockers,
+            [
+                "DEL-02.01 is CHECKING (line 2: DEL-02.01)",
+                "DEL-09.09 does not exist (line 3: DEL-09.09)"
+            ]
+        );
+        assert!(graph.blocked_by(&c.id).is_empty());
+    }
+
+    #[test]
+    fn finds_transitive_downstream_impacts() {
+        let mut graph = DependencyGraph::new();
+        let a = deliverable(1, 1, DeliverableState::Issued);
+        let b = deliverable(1, 2, DeliverableState::InProgress);
+        let c = deliverable(1, 3, DeliverableState::InProgress);
+        let d = deliverable(1, 4, DeliverableState::InProgress);
+        graph.add(&a, Vec::new());
+        graph.add(&b, Dependency::parse_file(&b.id, "- DEL-01.01\n"));
+        graph.add(&c, Dependency::parse_file(&c.id, "- DEL-01.02 (optional)\n- DEL-01.01\n"));
+        graph.add(&d, Dependency::parse_file(&d.id, "- DEL-01.03\n"));
+
+        let impacts = graph.downstream_of(std::slice::from_ref(&a.id));
+        let reasons: Vec<(String, String)> = impacts
+            .iter()
+            .map(|i| (i.deliverable.to_string(), i.reason()))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                ("DEL-01.02".to_string(), "depends on DEL-01.01, which changed".to_string()),
+                ("DEL-01.03".to_string(), "depends on DEL-01.01, which changed".to_string()),
+                (
+                    "DEL-01.04".to_string(),
+                    "depends on DEL-01.03 -> DEL-01.01, which changed".to_string()
+                ),
+            ]
+        );
+        assert!(graph.downstream_of(std::slice::from_ref(&d.id)).is_empty());
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let mut graph = DependencyGraph::new();
+        let a = deliverable(1, 1, DeliverableState::Open);
+        let b = deliverable(1, 2, DeliverableState::Open);
+        let c = deliverable(1, 3, DeliverableState::Open);
+        graph.add(&a, Dependency::parse_file(&a.id, "- DEL-01.02\n"));
+        graph.add(&b, Dependency::parse_file(&b.id, "- DEL-01.03\n"));
+        graph.add(&c, Dependency::parse_file(&c.id, "- DEL-01.01\n"));
+
+        match graph.topological_order() {
+            Err(DomainError::DependencyCycle { cycle }) => {
+                assert_eq!(cycle.len(), 4);
+                assert_eq!(cycle.first(), cycle.last());
+            }
+            other => panic!("expected a cycle, got {:?}", other),
+        }
+    }
+}
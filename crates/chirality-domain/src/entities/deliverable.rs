@@ -1,9 +1,15 @@
 //! Deliverable entity - primary work unit with 6-state lifecycle.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use super::{DeliverableId, DocumentId, PackageId};
+use super::{
+    ActorId, DeliverableId, Document, DocumentId, DocumentState, IssuedRevision, PackageId,
+    Revision, RevisionScheme,
+};
+use crate::error::DomainError;
+use crate::readiness::{ReadinessChecks, ReadinessInput};
 use crate::state_machines::DeliverableState;
 
 /// Deliverable - primary work unit within a Package.
@@ -23,6 +29,17 @@ pub struct Deliverable {
     pub folder_path: PathBuf,
     pub documents: Vec<DocumentRef>,
     pub anticipated_artifacts: Vec<String>,
+    /// Current revision being worked on (or issued).
+    #[serde(default)]
+    pub revision: Revision,
+    #[serde(default)]
+    pub revision_scheme: RevisionScheme,
+    /// Append-only record of lifecycle transitions.
+    #[serde(default)]
+    history: Vec<StateTransition>,
+    /// Prior issued revisions, oldest first. Never modified once recorded.
+    #[serde(default)]
+    issued_revisions: Vec<IssuedRevision>,
 }
 
 impl Deliverable {
@@ -42,6 +59,10 @@ impl Deliverable {
             folder_path,
             documents: Vec::new(),
             anticipated_artifacts: Vec::new(),
+            revision: Revision::default(),
+            revision_scheme: RevisionScheme::default(),
+            history: Vec::new(),
+            issued_revisions: Vec::new(),
         }
     }
 
@@ -65,9 +86,288 @@ impl Deliverable {
         self
     }
 
+    /// Restore previously recorded history (e.g. from `_STATUS.md`).
+    pub fn with_history(mut self, history: Vec<StateTransition>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Use a revision scheme, starting at its first revision.
+    pub fn with_revision_scheme(mut self, scheme: RevisionScheme) -> Self {
+        self.revision_scheme = scheme;
+        self.revision = scheme.first();
+        self
+    }
+
+    /// Restore the current revision and prior issued revisions (e.g. from `_STATUS.md`).
+    pub fn with_revisions(mut self, current: Revision, issued: Vec<IssuedRevision>) -> Self {
+        self.revision = current;
+        self.issued_revisions = issued;
+        self
+    }
+
     pub fn add_document(&mut self, doc_ref: DocumentRef) {
         self.documents.push(doc_ref);
     }
+
+    /// Transition history, oldest first.
+    pub fn history(&self) -> &[StateTransition] {
+        &self.history
+    }
+
+    /// Transition to a new state on behalf of an actor, recording it in history.
+    pub fn transition(
+        &mut self,
+        target: DeliverableState,
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> Result<&StateTransition, DomainError> {
+        let next = self.state.transition_to(target, &actor)?;
+        if next.requires_reason() && reason.as_deref().is_none_or(|r| r.trim().is_empty()) {
+            return Err(DomainError::PreconditionFailed {
+                message: format!("moving {} to {} requires a reason", self.id, next),
+            });
+        }
+        if next == DeliverableState::Issued {
+            return Err(DomainError::PreconditionFailed {
+                message: format!(
+                    "{} must be issued with its core documents (use Deliverable::issue)",
+                    self.id
+                ),
+            });
+        }
+        if (self.state, next) == (DeliverableState::InProgress, DeliverableState::Checking) {
+            return Err(DomainError::PreconditionFailed {
+                message: format!(
+                    "{} must pass readiness checks before CHECKING (use Deliverable::submit_for_checking)",
+                    self.id
+                ),
+            });
+        }
+        if self.state == DeliverableState::OnHold && next.is_holdable() {
+            // Without history there is no way to honour "resume where you
+            // paused", so refuse rather than allow any holdable state.
+            let prior = self.held_from().ok_or_else(|| DomainError::PreconditionFailed {
+                message: format!(
+                    "{} is ON_HOLD but the state it was held from is not in its history; \
+                     record the hold in _STATUS.md before resuming",
+                    self.id
+                ),
+            })?;
+            if next != prior {
+                return Err(DomainError::InvalidStateTransition {
+                    entity: "Deliverable".to_string(),
+                    from: format!("{:?} (held from {:?})", self.state, prior),
+                    to: format!("{:?}", next),
+                });
+            }
+        }
+        Ok(self.record(next, actor, reason))
+    }
+
+    /// Move an IN_PROGRESS deliverable to CHECKING once it passes `checks`.
+    ///
+    /// Any finding fails the transition with `PreconditionFailed` listing
+    /// them; run `checks` directly for the structured report.
+    pub fn submit_for_checking(
+        &mut self,
+        checks: &ReadinessChecks,
+        input: &ReadinessInput<'_>,
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> Result<&StateTransition, DomainError> {
+        if self.state != DeliverableState::InProgress {
+            return Err(DomainError::InvalidStateTransition {
+                entity: "Deliverable".to_string(),
+                from: format!("{:?}", self.state),
+                to: format!("{:?}", DeliverableState::Checking),
+            });
+        }
+        let next = self.state.transition_to(DeliverableState::Checking, &actor)?;
+        let report = checks.run(input);
+        if !report.is_ready() {
+            return Err(DomainError::PreconditionFailed {
+                message: format!("{} is not ready for CHECKING:\n{}", self.id, report.summary()),
+            });
+        }
+        Ok(self.record(next, actor, reason))
+    }
+
+    /// Issue a deliverable under review together with its core documents.
+    ///
+    /// All four core documents must be REVIEWED; they are flipped to ISSUED
+    /// only once every check has passed, so a refusal changes nothing.
+    pub fn issue(
+        &mut self,
+        documents: &mut [Document],
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> Result<&StateTransition, DomainError> {
+        let next = self.state.transition_to(DeliverableState::Issued, &actor)?;
+        let core = self.core_documents_in(documents, DocumentState::Reviewed)?;
+        for &i in &core {
+            documents[i].issue(actor.clone());
+        }
+        Ok(self.record(next, actor, reason))
+    }
+
+    fn record(
+        &mut self,
+        next: DeliverableState,
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> &StateTransition {
+        self.history.push(StateTransition {
+            from: self.state,
+            to: next,
+            actor,
+            at: Utc::now(),
+            reason,
+        });
+        self.state = next;
+        self.history.last().expect("transition was just recorded")
+    }
+
+    /// State an ON_HOLD deliverable will resume into, if known from history.
+    pub fn held_from(&self) -> Option<DeliverableState> {
+        if self.state != DeliverableState::OnHold {
+            return None;
+        }
+        self.history
+            .iter()
+            .rev()
+            .find(|t| t.to == DeliverableState::OnHold)
+            .map(|t| t.from)
+    }
+
+    /// Previously issued revisions, oldest first.
+    pub fn issued_revisions(&self) -> &[IssuedRevision] {
+        &self.issued_revisions
+    }
+
+    /// Look up a previously issued revision by label.
+    pub fn issued_revision(&self, label: &str) -> Option<&IssuedRevision> {
+        self.issued_revisions
+            .iter()
+            .find(|r| r.revision().as_str() == label)
+    }
+
+    /// Reopen an ISSUED deliverable as the next revision.
+    ///
+    /// The issued revision is frozen (with the content hashes of its core
+    /// documents), the revision label advances per `revision_scheme`, the four
+    /// core documents return to DRAFT and the deliverable returns to
+    /// IN_PROGRESS. Revising is a human decision, and is refused when the
+    /// history does not record who issued the current revision and when.
+    pub fn revise(
+        &mut self,
+        documents: &mut [Document],
+        actor: ActorId,
+        reason: Option<String>,
+    ) -> Result<&Revision, DomainError> {
+        if self.state != DeliverableState::Issued {
+            return Err(DomainError::InvalidStateTransition {
+                entity: "Deliverable".to_string(),
+                from: format!("{:?}", self.state),
+                to: "revision".to_string(),
+            });
+        }
+        if !actor.is_human() {
+            return Err(DomainError::HumanActorRequired {
+                operation: format!("revise {} (requested by {})", self.id, actor),
+            });
+        }
+        let next = self.revision_scheme.next(&self.revision)?;
+        let core = self.core_documents_in(documents, DocumentState::Issued)?;
+
+        let (issued_at, issued_by) = self
+            .history
+            .iter()
+            .rev()
+            .find(|t| t.to == DeliverableState::Issued)
+            .map(|t| (t.at, t.actor.clone()))
+            .ok_or_else(|| DomainError::PreconditionFailed {
+                message: format!("{} has no recorded ISSUED transition to freeze", self.id),
+            })?;
+        let snapshot = core
+            .iter()
+            .map(|&i| (documents[i].document_type, documents[i].content_hash.clone()))
+            .collect();
+        self.issued_revisions.push(IssuedRevision::new(
+            self.revision.clone(),
+            issued_at,
+            issued_by,
+            snapshot,
+        ));
+
+        for &i in &core {
+            documents[i].reopen(next.clone(), actor.clone());
+        }
+        let reason = match reason {
+            Some(r) => format!("Revision {}: {}", next, r),
+            None => format!("Revision {}", next),
+        };
+        self.record(DeliverableState::InProgress, actor, Some(reason));
+        self.revision = next;
+        Ok(&self.revision)
+    }
+
+    /// Indices of this deliverable's four core documents, each required to
+    /// be present exactly once and in the `expected` state.
+    fn core_documents_in(
+        &self,
+        documents: &[Document],
+        expected: DocumentState,
+    ) -> Result<Vec<usize>, DomainError> {
+        let mut indices = Vec::new();
+        for document_type in DocumentType::ALL.into_iter().filter(DocumentType::is_core) {
+            let matching: Vec<usize> = documents
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| d.deliverable_id == self.id && d.document_type == document_type)
+                .map(|(i, _)| i)
+                .collect();
+            match matching.as_slice() {
+                [i] if documents[*i].state == expected => indices.push(*i),
+                [i] => {
+                    return Err(DomainError::PreconditionFailed {
+                        message: format!(
+                            "{} of {} is {:?}, expected {:?}",
+                            document_type.filename(),
+                            self.id,
+                            documents[*i].state,
+                            expected
+                        ),
+                    })
+                }
+                [] => {
+                    return Err(DomainError::PreconditionFailed {
+                        message: format!("{} of {} is missing", document_type.filename(), self.id),
+                    })
+                }
+                _ => {
+                    return Err(DomainError::PreconditionFailed {
+                        message: format!(
+                            "{} of {} was supplied more than once",
+                            document_type.filename(),
+                            self.id
+                        ),
+                    })
+                }
+            }
+        }
+        Ok(indices)
+    }
+}
+
+/// A recorded deliverable state change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub from: DeliverableState,
+    pub to: DeliverableState,
+    pub actor: ActorId,
+    pub at: DateTime<Utc>,
+    pub reason: Option<String>,
 }
 
 /// Reference to a document within a deliverable.
@@ -97,6 +397,24 @@ pub enum DocumentType {
 }
 
 impl DocumentType {
+    /// Every document type, core documents first.
+    pub const ALL: [DocumentType; 9] = [
+        DocumentType::Datasheet,
+        DocumentType::Specification,
+        DocumentType::Guidance,
+        DocumentType::Procedure,
+        DocumentType::Context,
+        DocumentType::Status,
+        DocumentType::Dependencies,
+        DocumentType::References,
+        DocumentType::Semantic,
+    ];
+
+    /// Look up a document type by its filename.
+    pub fn from_filename(name: &str) -> Option<DocumentType> {
+        Self::ALL.into_iter().find(|t| t.filename() == name)
+    }
+
     /// Get the filename for this document type.
     pub fn filename(&self) -> &'static str {
         match self {
@@ -128,3 +446,252 @@ impl DocumentType {
         !self.is_core()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ContentHash;
+
+    fn deliverable() -> Deliverable {
+        Deliverable::new(PackageId::from_legacy(1), "Foundation", PathBuf::from("DEL-01.01"))
+    }
+
+    #[test]
+    fn transitions_are_recorded_in_order() {
+        let mut deliverable = deliverable();
+        deliverable
+            .transition(DeliverableState::Initialized, ActorId::agent("4_DOCUMENTS"), None)
+            .unwrap();
+        deliverable
+            .transition(
+                DeliverableState::InProgress,
+                ActorId::human("alice"),
+                Some("Drafts look sane".to_string()),
+            )
+            .unwrap();
+
+        let history = deliverable.history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].from, DeliverableState::Open);
+        assert_eq!(history[1].to, DeliverableState::InProgress);
+        assert_eq!(history[1].reason.as_deref(), Some("Drafts look sane"));
+    }
+
+    #[test]
+    fn rejected_transition_leaves_history_untouched() {
+        let mut deliverable = deliverable();
+        deliverable.state = DeliverableState::Checking;
+
+        let result = deliverable.transition(DeliverableState::Issued, ActorId::agent("QA"), None);
+        assert!(matches!(result, Err(DomainError::HumanActorRequired { .. })));
+        assert_eq!(deliverable.state, DeliverableState::Checking);
+        assert!(deliverable.history().is_empty());
+    }
+
+    #[test]
+    fn hold_resumes_to_prior_state() {
+        let alice = || ActorId::human("alice");
+        let mut deliverable = deliverable();
+        deliverable.state = DeliverableState::InProgress;
+
+        assert!(matches!(
+            deliverable.transition(DeliverableState::OnHold, alice(), None),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+        deliverable
+            .transition(DeliverableState::OnHold, alice(), Some("Awaiting survey".into()))
+            .unwrap();
+        assert_eq!(deliverable.held_from(), Some(DeliverableState::InProgress));
+
+        assert!(matches!(
+            deliverable.transition(DeliverableState::Checking, alice(), None),
+            Err(DomainError::InvalidStateTransition { .. })
+        ));
+        deliverable
+            .transition(DeliverableState::InProgress, alice(), None)
+            .unwrap();
+        assert_eq!(deliverable.held_from(), None);
+    }
+
+    #[test]
+    fn resume_without_hold_history_fails_closed() {
+        let mut deliverable = deliverable();
+        deliverable.state = DeliverableState::OnHold;
+        assert_eq!(deliverable.held_from(), None);
+
+        assert!(matches!(
+            deliverable.transition(DeliverableState::Checking, ActorId::human("alice"), None),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+        assert_eq!(deliverable.state, DeliverableState::OnHold);
+        deliverable
+            .transition(DeliverableState::Cancelled, ActorId::human("alice"), Some("Dropped".into()))
+            .unwrap();
+    }
+
+    #[test]
+    fn cancel_requires_reason() {
+        let mut deliverable = deliverable();
+        assert!(deliverable
+            .transition(DeliverableState::Cancelled, ActorId::human("alice"), Some(" ".into()))
+            .is_err());
+        deliverable
+            .transition(
+                DeliverableState::Cancelled,
+                ActorId::human("alice"),
+                Some("Descoped by client".into()),
+            )
+            .unwrap();
+        assert!(deliverable.state.is_terminal());
+    }
+
+    #[test]
+    fn issue_requires_reviewed_core_documents_and_issues_them() {
+        let (mut deliverable, mut documents) = issued_deliverable();
+        deliverable.state = DeliverableState::Checking;
+        for doc in &mut documents {
+            doc.state = DocumentState::Reviewed;
+        }
+        documents[1].state = DocumentState::Draft;
+
+        assert!(matches!(
+            deliverable.issue(&mut documents, ActorId::human("alice"), None),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+        assert_eq!(documents[0].state, DocumentState::Reviewed);
+        assert!(matches!(
+            deliverable.transition(DeliverableState::Issued, ActorId::human("alice"), None),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+
+        documents[1].state = DocumentState::Reviewed;
+        deliverable
+            .issue(&mut documents, ActorId::human("alice"), None)
+            .unwrap();
+        assert_eq!(deliverable.state, DeliverableState::Issued);
+        assert!(documents.iter().all(|d| d.state == DocumentState::Issued));
+    }
+
+    #[test]
+    fn checking_requires_readiness() {
+        let mut deliverable = deliverable();
+        deliverable.state = DeliverableState::InProgress;
+        assert!(matches!(
+            deliverable.transition(DeliverableState::Checking, ActorId::human("alice"), None),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+
+        let snapshot = deliverable.clone();
+        let input = ReadinessInput::new(&snapshot).with_document(DocumentType::Datasheet, "TBD");
+        let result = deliverable.submit_for_checking(
+            &ReadinessChecks::standard(),
+            &input,
+            ActorId::human("alice"),
+            None,
+        );
+        assert!(matches!(result, Err(DomainError::PreconditionFailed { .. })));
+        assert_eq!(deliverable.state, DeliverableState::InProgress);
+
+        deliverable
+            .submit_for_checking(&ReadinessChecks::empty(), &input, ActorId::human("alice"), None)
+            .unwrap();
+        assert_eq!(deliverable.state, DeliverableState::Checking);
+    }
+
+    fn issued_deliverable() -> (Deliverable, Vec<Document>) {
+        let mut deliverable =
+            Deliverable::new(PackageId::from_legacy(1), "Foundation", PathBuf::from("DEL-01.01"))
+                .with_revision_scheme(RevisionScheme::AlphaThenNumeric { lettered: 2 })
+                .with_history(vec![StateTransition {
+                    from: DeliverableState::Checking,
+                    to: DeliverableState::Issued,
+                    actor: ActorId::human("bob"),
+                    at: Utc::now(),
+                    reason: None,
+                }]);
+        deliverable.state = DeliverableState::Issued;
+        let documents = DocumentType::ALL
+            .into_iter()
+            .filter(DocumentType::is_core)
+            .map(|t| {
+                let mut doc = Document::new(
+                    deliverable.id.clone(),
+                    t,
+                    PathBuf::from(t.filename()),
+                    ContentHash::from_bytes(t.filename().as_bytes()),
+                    deliverable.revision.clone(),
+                    ActorId::human("alice"),
+                );
+                doc.state = DocumentState::Issued;
+                doc
+            })
+            .collect();
+        (deliverable, documents)
+    }
+
+    #[test]
+    fn revise_freezes_issued_revision_and_reopens_documents() {
+        let (mut deliverable, mut documents) = issued_deliverable();
+
+        let next = deliverable
+            .revise(&mut documents, ActorId::human("alice"), Some("Client comments".into()))
+            .unwrap()
+            .clone();
+
+        assert_eq!(next.as_str(), "B");
+        assert_eq!(deliverable.state, DeliverableState::InProgress);
+        assert!(documents
+            .iter()
+            .all(|d| d.state == DocumentState::Draft && d.revision == next));
+        let frozen = deliverable.issued_revision("A").unwrap();
+        assert_eq!(frozen.issued_by(), &ActorId::human("bob"));
+        assert_eq!(
+            frozen.document_hash(DocumentType::Datasheet),
+            Some(&ContentHash::from_bytes(b"Datasheet.md"))
+        );
+        let last = deliverable.history().last().unwrap();
+        assert_eq!(last.from, DeliverableState::Issued);
+        assert_eq!(last.reason.as_deref(), Some("Revision B: Client comments"));
+    }
+
+    #[test]
+    fn revise_requires_human_and_issued_documents() {
+        let (mut deliverable, mut documents) = issued_deliverable();
+        assert!(matches!(
+            deliverable.revise(&mut documents, ActorId::agent("4_DOCUMENTS"), None),
+            Err(DomainError::HumanActorRequired { .. })
+        ));
+
+        documents[2].state = DocumentState::Reviewed;
+        assert!(matches!(
+            deliverable.revise(&mut documents, ActorId::human("alice"), None),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+        assert_eq!(deliverable.state, DeliverableState::Issued);
+        assert!(deliverable.issued_revisions().is_empty());
+        assert_eq!(documents[0].state, DocumentState::Issued);
+    }
+
+    #[test]
+    fn revise_refuses_without_recorded_issue() {
+        let (mut deliverable, mut documents) = issued_deliverable();
+        deliverable = deliverable.with_history(Vec::new());
+        assert!(matches!(
+            deliverable.revise(&mut documents, ActorId::human("alice"), None),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+        assert!(deliverable.issued_revisions().is_empty());
+        assert_eq!(deliverable.revision.as_str(), "A");
+        assert!(documents.iter().all(|d| d.state == DocumentState::Issued));
+    }
+
+    #[test]
+    fn revise_is_rejected_before_issue() {
+        let (mut deliverable, mut documents) = issued_deliverable();
+        deliverable.state = DeliverableState::Checking;
+        assert!(matches!(
+            deliverable.revise(&mut documents, ActorId::human("alice"), None),
+            Err(DomainError::InvalidStateTransition { .. })
+        ));
+    }
+}
@@ -4,8 +4,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use super::{ActorId, ContentHash, DeliverableId, DocumentId};
+use super::{ActorId, ContentHash, DeliverableId, DocumentId, Revision};
 use crate::entities::DocumentType;
+use crate::error::DomainError;
 
 /// Document - a content-addressed document within a deliverable.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +17,14 @@ pub struct Document {
     pub file_path: PathBuf,
     pub content_hash: ContentHash,
     pub state: DocumentState,
+    /// Deliverable revision this content belongs to.
+    #[serde(default)]
+    pub revision: Revision,
+    /// Human who last moved the document to REVIEWED (cleared on return to DRAFT).
+    #[serde(default)]
+    pub reviewed_by: Option<ActorId>,
+    #[serde(default)]
+    pub reviewed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: ActorId,
@@ -23,11 +32,14 @@ pub struct Document {
 }
 
 impl Document {
+    /// Create a DRAFT document at the owning deliverable's current revision,
+    /// so it follows whatever revision scheme the project uses.
     pub fn new(
         deliverable_id: DeliverableId,
         document_type: DocumentType,
         file_path: PathBuf,
         content_hash: ContentHash,
+        revision: Revision,
         created_by: ActorId,
     ) -> Self {
         let now = Utc::now();
@@ -38,6 +50,9 @@ impl Document {
             file_path,
             content_hash,
             state: DocumentState::Draft,
+            revision,
+            reviewed_by: None,
+            reviewed_at: None,
             created_at: now,
             updated_at: now,
             created_by: created_by.clone(),
@@ -50,6 +65,69 @@ impl Document {
         self.updated_at = Utc::now();
         self.updated_by = updated_by;
     }
+
+    /// Move the document between DRAFT and REVIEWED on behalf of an actor.
+    ///
+    /// Reviewing is a human decision and records the reviewer. ISSUED is only
+    /// reached through `Deliverable::issue`, which issues all four core
+    /// documents together.
+    pub fn transition(&mut self, target: DocumentState, actor: ActorId) -> Result<(), DomainError> {
+        if target == DocumentState::Issued {
+            return Err(DomainError::PreconditionFailed {
+                message: format!(
+                    "{} of {} is issued together with its deliverable",
+                    self.document_type.filename(),
+                    self.deliverable_id
+                ),
+            });
+        }
+        let next = self.state.transition_to(target)?;
+        if next == DocumentState::Reviewed && !actor.is_human() {
+            return Err(DomainError::HumanActorRequired {
+                operation: format!(
+                    "review {} of {} (requested by {})",
+                    self.document_type.filename(),
+                    self.deliverable_id,
+                    actor
+                ),
+            });
+        }
+        let now = Utc::now();
+        match next {
+            DocumentState::Reviewed => {
+                self.reviewed_by = Some(actor.clone());
+                self.reviewed_at = Some(now);
+            }
+            _ => {
+                self.reviewed_by = None;
+                self.reviewed_at = None;
+            }
+        }
+        self.state = next;
+        self.updated_at = now;
+        self.updated_by = actor;
+        Ok(())
+    }
+
+    /// Mark a REVIEWED document as ISSUED alongside its deliverable.
+    pub(crate) fn issue(&mut self, actor: ActorId) {
+        self.state = DocumentState::Issued;
+        self.updated_at = Utc::now();
+        self.updated_by = actor;
+    }
+
+    /// Return an issued document to DRAFT for a new deliverable revision.
+    ///
+    /// Only reachable through `Deliverable::revise`, which keeps the four
+    /// core documents in step.
+    pub(crate) fn reopen(&mut self, revision: Revision, actor: ActorId) {
+        self.state = DocumentState::Draft;
+        self.revision = revision;
+        self.reviewed_by = None;
+        self.reviewed_at = None;
+        self.updated_at = Utc::now();
+        self.updated_by = actor;
+    }
 }
 
 /// Document lifecycle state.
@@ -73,4 +151,65 @@ impl DocumentState {
                 | (DocumentState::Reviewed, DocumentState::Issued)
         )
     }
+
+    /// Attempt to transition to a new state.
+    pub fn transition_to(self, target: DocumentState) -> Result<DocumentState, DomainError> {
+        if self.can_transition_to(&target) {
+            Ok(target)
+        } else {
+            Err(DomainError::InvalidStateTransition {
+                entity: "Document".to_string(),
+                from: format!("{:?}", self),
+                to: format!("{:?}", target),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Document {
+        Document::new(
+            DeliverableId::from_legacy(1, 1),
+            DocumentType::Datasheet,
+            PathBuf::from("Datasheet.md"),
+            ContentHash::from_bytes(b"ds"),
+            Revision::default(),
+            ActorId::agent("4_DOCUMENTS"),
+        )
+    }
+
+    #[test]
+    fn review_records_human_reviewer() {
+        let mut doc = document();
+        assert!(matches!(
+            doc.transition(DocumentState::Reviewed, ActorId::agent("QA")),
+            Err(DomainError::HumanActorRequired { .. })
+        ));
+
+        doc.transition(DocumentState::Reviewed, ActorId::human("alice"))
+            .unwrap();
+        assert_eq!(doc.reviewed_by, Some(ActorId::human("alice")));
+
+        doc.transition(DocumentState::Draft, ActorId::agent("4_DOCUMENTS"))
+            .unwrap();
+        assert_eq!(doc.state, DocumentState::Draft);
+        assert!(doc.reviewed_by.is_none());
+    }
+
+    #[test]
+    fn documents_cannot_be_issued_directly() {
+        let mut doc = document();
+        doc.state = DocumentState::Reviewed;
+        assert!(matches!(
+            doc.transition(DocumentState::Issued, ActorId::human("alice")),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+        assert!(matches!(
+            DocumentState::Draft.transition_to(DocumentState::Issued),
+            Err(DomainError::InvalidStateTransition { .. })
+        ));
+    }
 }
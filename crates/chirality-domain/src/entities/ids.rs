@@ -2,8 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 use ulid::Ulid;
 
+use crate::error::DomainError;
+
 /// Project identifier (proj:<ULID>)
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ProjectId(String);
@@ -70,7 +73,7 @@ impl fmt::Display for PackageId {
 }
 
 /// Deliverable identifier (DEL-##.## or del:<ULID>)
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct DeliverableId(String);
 
 impl DeliverableId {
@@ -113,6 +116,12 @@ impl DocumentId {
         Self(format!("doc:{}", Ulid::new()))
     }
 
+    /// Id derived from the owning deliverable and file name
+    /// (`doc:DEL-01.01/Datasheet.md`), so every scan of a folder agrees.
+    pub fn for_file(deliverable_id: &DeliverableId, file_name: &str) -> Self {
+        Self(format!("doc:{}/{}", deliverable_id, file_name))
+    }
+
     pub fn from_string(s: impl Into<String>) -> Self {
         Self(s.into())
     }
@@ -252,6 +261,26 @@ impl fmt::Display for ActorId {
     }
 }
 
+impl FromStr for ActorId {
+    type Err = DomainError;
+
+    /// Parse the `KIND:id` form produced by `Display` (e.g. `HUMAN:alice`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, id) = s.trim().split_once(':').ok_or_else(|| DomainError::InvalidState {
+            message: format!("actor must be KIND:id, found {:?}", s),
+        })?;
+        if id.is_empty() {
+            return Err(DomainError::InvalidState {
+                message: format!("actor id is empty in {:?}", s),
+            });
+        }
+        Ok(Self {
+            kind: kind.parse()?,
+            id: id.to_string(),
+        })
+    }
+}
+
 /// Actor kind
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -271,6 +300,21 @@ impl fmt::Display for ActorKind {
     }
 }
 
+impl FromStr for ActorKind {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "HUMAN" => Ok(ActorKind::Human),
+            "AGENT" => Ok(ActorKind::Agent),
+            "SYSTEM" => Ok(ActorKind::System),
+            _ => Err(DomainError::InvalidState {
+                message: format!("unknown actor kind: {:?}", s),
+            }),
+        }
+    }
+}
+
 // Need hex for content hash
 mod hex {
     const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
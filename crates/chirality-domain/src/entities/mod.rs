@@ -6,6 +6,7 @@ mod deliverable;
 mod document;
 mod session;
 mod ids;
+mod revision;
 
 pub use project::*;
 pub use package::*;
@@ -13,3 +14,4 @@ pub use deliverable::*;
 pub use document::*;
 pub use session::*;
 pub use ids::*;
+pub use revision::*;
@@ -48,7 +48,8 @@ impl Package {
         format!("{}_{}", PackageId::new(), Self::sanitize_label(label))
     }
 
-    fn sanitize_label(label: &str) -> String {
+    /// Label as used in folder names (non-alphanumerics become `_`).
+    pub(crate) fn sanitize_label(label: &str) -> String {
         label
             .chars()
             .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use super::{ActorId, ProjectId};
+use super::{ActorId, ProjectId, RevisionScheme};
 
 /// Project - aggregate root containing decomposition and workspace path.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +14,9 @@ pub struct Project {
     pub description: Option<String>,
     pub workspace_path: PathBuf,
     pub decomposition_path: Option<PathBuf>,
+    /// Revision labelling used for deliverables in this project.
+    #[serde(default)]
+    pub revision_scheme: RevisionScheme,
     pub created_at: DateTime<Utc>,
     pub created_by: ActorId,
 }
@@ -30,6 +33,7 @@ impl Project {
             description: None,
             workspace_path,
             decomposition_path: None,
+            revision_scheme: RevisionScheme::default(),
             created_at: Utc::now(),
             created_by,
         }
@@ -44,4 +48,9 @@ impl Project {
         self.decomposition_path = Some(path);
         self
     }
+
+    pub fn with_revision_scheme(mut self, scheme: RevisionScheme) -> Self {
+        self.revision_scheme = scheme;
+        self
+    }
 }
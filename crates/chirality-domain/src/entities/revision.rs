@@ -0,0 +1,204 @@
+//! Revision labels for issued deliverables.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::{ActorId, ContentHash, DocumentType};
+use crate::error::DomainError;
+
+/// Revision label (e.g. `A`, `B`, `0`, `1`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Revision(String);
+
+impl Revision {
+    pub fn from_string(s: impl Into<String>) -> Self {
+        Self(s.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Revision {
+    fn default() -> Self {
+        RevisionScheme::default().first()
+    }
+}
+
+impl fmt::Display for Revision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// How successive revisions are labelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type")]
+pub enum RevisionScheme {
+    /// A, B, C, … Z, AA, AB, …
+    #[default]
+    Alphabetic,
+    /// 0, 1, 2, …
+    Numeric,
+    /// Letters for the first `lettered` revisions, then numbers (A, B, 0, 1, …).
+    AlphaThenNumeric { lettered: u32 },
+}
+
+impl RevisionScheme {
+    /// Label of the first revision.
+    pub fn first(&self) -> Revision {
+        self.label_at(0)
+    }
+
+    /// Label following `current`.
+    pub fn next(&self, current: &Revision) -> Result<Revision, DomainError> {
+        let index = self.index_of(current).ok_or_else(|| DomainError::InvalidState {
+            message: format!("revision {:?} is not part of scheme {:?}", current.as_str(), self),
+        })?;
+        Ok(self.label_at(index + 1))
+    }
+
+    fn label_at(&self, index: u32) -> Revision {
+        match *self {
+            RevisionScheme::Alphabetic => Revision(alpha_label(index)),
+            RevisionScheme::Numeric => Revision(index.to_string()),
+            RevisionScheme::AlphaThenNumeric { lettered } if index < lettered => {
+                Revision(alpha_label(index))
+            }
+            RevisionScheme::AlphaThenNumeric { lettered } => Revision((index - lettered).to_string()),
+        }
+    }
+
+    fn index_of(&self, revision: &Revision) -> Option<u32> {
+        let label = revision.as_str();
+        match *self {
+            RevisionScheme::Alphabetic => alpha_index(label),
+            RevisionScheme::Numeric => numeric_index(label),
+            RevisionScheme::AlphaThenNumeric { lettered } => match alpha_index(label) {
+                Some(i) if i < lettered => Some(i),
+                Some(_) => None,
+                None => numeric_index(label).map(|n| n + lettered),
+            },
+        }
+    }
+}
+
+/// Bijective base-26: 0 → A, 25 → Z, 26 → AA.
+fn alpha_label(mut index: u32) -> String {
+    let mut chars = Vec::new();
+    loop {
+        chars.push((b'A' + (index % 26) as u8) as char);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    chars.iter().rev().collect()
+}
+
+fn alpha_index(label: &str) -> Option<u32> {
+    if label.is_empty() || !label.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let mut index: u32 = 0;
+    for c in label.bytes() {
+        index = index.checked_mul(26)?.checked_add((c - b'A') as u32 + 1)?;
+    }
+    Some(index - 1)
+}
+
+fn numeric_index(label: &str) -> Option<u32> {
+    if label.is_empty() || !label.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    label.parse().ok()
+}
+
+/// Immutable record of a revision as it was issued.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuedRevision {
+    revision: Revision,
+    issued_at: DateTime<Utc>,
+    issued_by: ActorId,
+    documents: Vec<(DocumentType, ContentHash)>,
+}
+
+impl IssuedRevision {
+    pub fn new(
+        revision: Revision,
+        issued_at: DateTime<Utc>,
+        issued_by: ActorId,
+        documents: Vec<(DocumentType, ContentHash)>,
+    ) -> Self {
+        Self {
+            revision,
+            issued_at,
+            issued_by,
+            documents,
+        }
+    }
+
+    pub fn revision(&self) -> &Revision {
+        &self.revision
+    }
+
+    pub fn issued_at(&self) -> DateTime<Utc> {
+        self.issued_at
+    }
+
+    pub fn issued_by(&self) -> &ActorId {
+        &self.issued_by
+    }
+
+    /// Content hashes of the documents as issued.
+    pub fn documents(&self) -> &[(DocumentType, ContentHash)] {
+        &self.documents
+    }
+
+    /// Content hash of one document as issued.
+    pub fn document_hash(&self, document_type: DocumentType) -> Option<&ContentHash> {
+        self.documents
+            .iter()
+            .find(|(t, _)| *t == document_type)
+            .map(|(_, hash)| hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(scheme: RevisionScheme, count: usize) -> Vec<String> {
+        let mut revision = scheme.first();
+        let mut labels = vec![revision.to_string()];
+        for _ in 1..count {
+            revision = scheme.next(&revision).unwrap();
+            labels.push(revision.to_string());
+        }
+        labels
+    }
+
+    #[test]
+    fn alphabetic_wraps_to_double_letters() {
+        let labels = sequence(RevisionScheme::Alphabetic, 28);
+        assert_eq!(&labels[..3], ["A", "B", "C"]);
+        assert_eq!(&labels[25..], ["Z", "AA", "AB"]);
+    }
+
+    #[test]
+    fn alpha_then_numeric() {
+        let labels = sequence(RevisionScheme::AlphaThenNumeric { lettered: 2 }, 5);
+        assert_eq!(labels, ["A", "B", "0", "1", "2"]);
+    }
+
+    #[test]
+    fn rejects_labels_outside_scheme() {
+        let scheme = RevisionScheme::AlphaThenNumeric { lettered: 2 };
+        assert!(scheme.next(&Revision::from_string("C")).is_err());
+        assert!(RevisionScheme::Numeric
+            .next(&Revision::from_string("A"))
+            .is_err());
+    }
+}
@@ -4,9 +4,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use super::{ActorId, ContentHash, DeliverableId, PackageId, ProjectId, SessionId};
+use super::{ActorId, ContentHash, Deliverable, DeliverableId, PackageId, ProjectId, SessionId};
+use crate::error::DomainError;
 use crate::state_machines::SessionState;
-use crate::WriteScope;
+use crate::{ReadScope, WriteAttempt, WriteScope};
 
 /// AgentSession - execution context for an agent.
 ///
@@ -23,11 +24,19 @@ pub struct AgentSession {
     pub brief: Option<SessionBrief>,
     pub state: SessionState,
     pub write_scope: WriteScope,
+    #[serde(default)]
+    pub read_scope: ReadScope,
     pub outputs: Vec<SessionOutput>,
     pub git_branch: Option<String>,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub started_by: ActorId,
+    /// Why the session failed, when known (e.g. an unmet output contract).
+    #[serde(default)]
+    pub failure_reason: Option<String>,
+    /// Every write the session attempted, allowed or denied, in order.
+    #[serde(default)]
+    pub write_attempts: Vec<WriteAttempt>,
 }
 
 impl AgentSession {
@@ -47,11 +56,14 @@ impl AgentSession {
             brief: Some(brief),
             state: SessionState::Created,
             write_scope,
+            read_scope: ReadScope::default(),
             outputs: Vec::new(),
             git_branch: None,
             started_at: Utc::now(),
             completed_at: None,
             started_by,
+            failure_reason: None,
+            write_attempts: Vec::new(),
         }
     }
 
@@ -71,19 +83,54 @@ impl AgentSession {
             brief: None,
             state: SessionState::Created,
             write_scope,
+            read_scope: ReadScope::default(),
             outputs: Vec::new(),
             git_branch: None,
             started_at: Utc::now(),
             completed_at: None,
             started_by,
+            failure_reason: None,
+            write_attempts: Vec::new(),
         }
     }
 
+    pub fn with_read_scope(mut self, read_scope: ReadScope) -> Self {
+        self.read_scope = read_scope;
+        self
+    }
+
     pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
         self.git_branch = Some(branch.into());
         self
     }
 
+    /// Move the session from CREATED to ACTIVE.
+    ///
+    /// Deliverable-scoped sessions must be given their deliverable, and refuse
+    /// to start while it is on hold, cancelled or superseded.
+    pub fn start(&mut self, deliverable: Option<&Deliverable>) -> Result<(), DomainError> {
+        if let SessionScope::Deliverable { deliverable_id } = &self.scope {
+            let deliverable = deliverable
+                .filter(|d| &d.id == deliverable_id)
+                .ok_or_else(|| DomainError::PreconditionFailed {
+                    message: format!(
+                        "session {} is scoped to {} but that deliverable was not supplied",
+                        self.id, deliverable_id
+                    ),
+                })?;
+            if !deliverable.state.accepts_sessions() {
+                return Err(DomainError::PreconditionFailed {
+                    message: format!(
+                        "{} is {}; agents cannot run against it",
+                        deliverable.id, deliverable.state
+                    ),
+                });
+            }
+        }
+        self.state = self.state.transition_to(SessionState::Active, self.agent_class)?;
+        Ok(())
+    }
+
     pub fn add_output(&mut self, output: SessionOutput) {
         self.outputs.push(output);
     }
@@ -97,6 +144,17 @@ impl AgentSession {
         self.state = SessionState::Failed;
         self.completed_at = Some(Utc::now());
     }
+
+    /// Append checked writes to the audit trail.
+    pub fn record_write_attempts(&mut self, attempts: impl IntoIterator<Item = WriteAttempt>) {
+        self.write_attempts.extend(attempts);
+    }
+
+    /// Fail the session, recording why.
+    pub fn fail_with(&mut self, reason: impl Into<String>) {
+        self.fail();
+        self.failure_reason = Some(reason.into());
+    }
 }
 
 /// Agent type following chirality-app's three-layer hierarchy.
@@ -161,3 +219,44 @@ pub enum OutputType {
     Report,
     Metadata,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machines::DeliverableState;
+
+    fn session_for(deliverable: &Deliverable) -> AgentSession {
+        AgentSession::new_persona(
+            "WORKING_ITEMS",
+            AgentType::Manager,
+            SessionScope::Deliverable {
+                deliverable_id: deliverable.id.clone(),
+            },
+            WriteScope::DeliverableLocal {
+                deliverable_id: deliverable.id.clone(),
+                deliverable_path: deliverable.folder_path.clone(),
+            },
+            ActorId::human("alice"),
+        )
+    }
+
+    #[test]
+    fn start_refuses_held_deliverable() {
+        let mut deliverable =
+            Deliverable::new(PackageId::from_legacy(1), "Foundation", PathBuf::from("DEL-01.01"))
+                .with_legacy_id(1, 1);
+        deliverable.state = DeliverableState::OnHold;
+        let mut session = session_for(&deliverable);
+
+        assert!(matches!(
+            session.start(Some(&deliverable)),
+            Err(DomainError::PreconditionFailed { .. })
+        ));
+        assert!(session.start(None).is_err());
+        assert_eq!(session.state, SessionState::Created);
+
+        deliverable.state = DeliverableState::InProgress;
+        session.start(Some(&deliverable)).unwrap();
+        assert!(session.state.is_active());
+    }
+}
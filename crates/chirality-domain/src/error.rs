@@ -3,6 +3,9 @@
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::brief_schema::BriefViolation;
+use crate::scope_partition::PartitionIssue;
+
 /// Domain-level errors.
 #[derive(Debug, Error)]
 pub enum DomainError {
@@ -20,9 +23,19 @@ pub enum DomainError {
         reason: String,
     },
 
+    #[error("Read violation: cannot read {target_path:?} with scope {scope}: {reason}")]
+    ReadViolation {
+        target_path: PathBuf,
+        scope: String,
+        reason: String,
+    },
+
     #[error("Invalid session brief: {reason}")]
     InvalidBrief { reason: String },
 
+    #[error("Invalid session brief: {}", join_violations(.violations))]
+    BriefViolations { violations: Vec<BriefViolation> },
+
     #[error("Human actor required for {operation}")]
     HumanActorRequired { operation: String },
 
@@ -32,6 +45,41 @@ pub enum DomainError {
     #[error("Invalid entity state: {message}")]
     InvalidState { message: String },
 
+    #[error("Invalid _STATUS.md at line {line}: {reason}")]
+    InvalidStatusFile { line: usize, reason: String },
+
+    #[error("Invalid agent definition {file} at line {line}: {reason}")]
+    InvalidAgentDefinition {
+        file: String,
+        line: usize,
+        reason: String,
+    },
+
+    #[error("Scope partition violated: {}", join_issues(.issues))]
+    ScopePartitionViolations { issues: Vec<PartitionIssue> },
+
+    #[error("Invalid decomposition at line {line}: {reason}")]
+    InvalidDecomposition { line: usize, reason: String },
+
+    #[error("Dependency cycle: {}", .cycle.join(" -> "))]
+    DependencyCycle { cycle: Vec<String> },
+
     #[error("Precondition failed: {message}")]
     PreconditionFailed { message: String },
 }
+
+fn join_violations(violations: &[BriefViolation]) -> String {
+    violations
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn join_issues(issues: &[PartitionIssue]) -> String {
+    issues
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
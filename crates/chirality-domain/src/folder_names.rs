@@ -0,0 +1,215 @@
+//! Folder naming conventions for packages and deliverables.
+//!
+//! From chirality-app: the workspace tree mirrors the decomposition.
+//! Packages live in `PKG-###_Label` folders at the project root and
+//! deliverables live in `DEL-##.##_Label` folders inside their package.
+//! Folders created by the runtime may use the `pkg:<ULID>` / `del:<ULID>`
+//! identifier forms instead of the legacy numbering.
+
+use ulid::Ulid;
+
+use crate::entities::{DeliverableId, PackageId};
+
+/// Kind of entity a folder name is expected to describe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FolderKind {
+    Package,
+    Deliverable,
+}
+
+/// A recognised package folder name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageFolder {
+    pub id: PackageId,
+    /// Legacy package number (PKG-###), if the folder uses that form.
+    pub number: Option<u32>,
+    pub label: Option<String>,
+}
+
+/// A recognised deliverable folder name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliverableFolder {
+    pub id: DeliverableId,
+    /// Legacy package number (the `##` before the dot), if present.
+    pub package_number: Option<u32>,
+    /// Legacy deliverable number (the `##` after the dot), if present.
+    pub number: Option<u32>,
+    pub label: Option<String>,
+}
+
+/// Result of classifying a single folder name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FolderName {
+    Package(PackageFolder),
+    Deliverable(DeliverableFolder),
+    /// Looks like a package or deliverable folder but does not parse.
+    Malformed { expected: FolderKind, reason: String },
+    /// Not a package or deliverable folder at all.
+    Unrecognized,
+}
+
+impl FolderName {
+    /// Classify a folder name (the last path component, not a full path).
+    pub fn parse(name: &str) -> Self {
+        if let Some(rest) = name.strip_prefix("PKG-") {
+            return Self::parse_legacy_package(rest);
+        }
+        if let Some(rest) = name.strip_prefix("pkg:") {
+            return match parse_ulid_form(rest) {
+                Ok((ulid, label)) => FolderName::Package(PackageFolder {
+                    id: PackageId::from_string(format!("pkg:{}", ulid)),
+                    number: None,
+                    label,
+                }),
+                Err(reason) => FolderName::Malformed {
+                    expected: FolderKind::Package,
+                    reason,
+                },
+            };
+        }
+        if let Some(rest) = name.strip_prefix("DEL-") {
+            return Self::parse_legacy_deliverable(rest);
+        }
+        if let Some(rest) = name.strip_prefix("del:") {
+            return match parse_ulid_form(rest) {
+                Ok((ulid, label)) => FolderName::Deliverable(DeliverableFolder {
+                    id: DeliverableId::from_string(format!("del:{}", ulid)),
+                    package_number: None,
+                    number: None,
+                    label,
+                }),
+                Err(reason) => FolderName::Malformed {
+                    expected: FolderKind::Deliverable,
+                    reason,
+                },
+            };
+        }
+        FolderName::Unrecognized
+    }
+
+    fn parse_legacy_package(rest: &str) -> Self {
+        let (number, label) = split_label(rest);
+        match parse_number(number) {
+            Some(num) => FolderName::Package(PackageFolder {
+                id: PackageId::from_legacy(num),
+                number: Some(num),
+                label,
+            }),
+            None => FolderName::Malformed {
+                expected: FolderKind::Package,
+                reason: format!("expected PKG-###, found PKG-{}", number),
+            },
+        }
+    }
+
+    fn parse_legacy_deliverable(rest: &str) -> Self {
+        let (numbers, label) = split_label(rest);
+        let parsed = numbers
+            .split_once('.')
+            .and_then(|(pkg, del)| Some((parse_number(pkg)?, parse_number(del)?)));
+        match parsed {
+            Some((package_num, deliverable_num)) => FolderName::Deliverable(DeliverableFolder {
+                id: DeliverableId::from_legacy(package_num, deliverable_num),
+                package_number: Some(package_num),
+                number: Some(deliverable_num),
+                label,
+            }),
+            None => FolderName::Malformed {
+                expected: FolderKind::Deliverable,
+                reason: format!("expected DEL-##.##, found DEL-{}", numbers),
+            },
+        }
+    }
+}
+
+/// Split `123_Some_Label` into (`123`, Some(`Some_Label`)).
+fn split_label(rest: &str) -> (&str, Option<String>) {
+    match rest.split_once('_') {
+        Some((head, label)) if !label.is_empty() => (head, Some(label.to_string())),
+        Some((head, _)) => (head, None),
+        None => (rest, None),
+    }
+}
+
+fn parse_number(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn parse_ulid_form(rest: &str) -> Result<(Ulid, Option<String>), String> {
+    let (raw, label) = split_label(rest);
+    Ulid::from_string(raw)
+        .map(|ulid| (ulid, label))
+        .map_err(|e| format!("invalid ULID {:?}: {}", raw, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_legacy_package_with_label() {
+        let parsed = FolderName::parse("PKG-007_Civil_Works");
+        assert_eq!(
+            parsed,
+            FolderName::Package(PackageFolder {
+                id: PackageId::from_legacy(7),
+                number: Some(7),
+                label: Some("Civil_Works".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_legacy_deliverable_and_canonicalises_id() {
+        match FolderName::parse("DEL-1.02_Foundation") {
+            FolderName::Deliverable(folder) => {
+                assert_eq!(folder.id.as_str(), "DEL-01.02");
+                assert_eq!(folder.package_number, Some(1));
+                assert_eq!(folder.label.as_deref(), Some("Foundation"));
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_ulid_forms() {
+        let ulid = Ulid::new();
+        match FolderName::parse(&format!("del:{}", ulid)) {
+            FolderName::Deliverable(folder) => {
+                assert_eq!(folder.id.as_str(), format!("del:{}", ulid));
+                assert_eq!(folder.package_number, None);
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+        assert!(matches!(
+            FolderName::parse(&format!("pkg:{}_Mechanical", ulid)),
+            FolderName::Package(_)
+        ));
+    }
+
+    #[test]
+    fn reports_malformed_names() {
+        assert!(matches!(
+            FolderName::parse("PKG-abc_Label"),
+            FolderName::Malformed {
+                expected: FolderKind::Package,
+                ..
+            }
+        ));
+        assert!(matches!(
+            FolderName::parse("DEL-0101"),
+            FolderName::Malformed {
+                expected: FolderKind::Deliverable,
+                ..
+            }
+        ));
+        assert!(matches!(
+            FolderName::parse("del:not-a-ulid"),
+            FolderName::Malformed { .. }
+        ));
+        assert_eq!(FolderName::parse("execution"), FolderName::Unrecognized);
+    }
+}
@@ -7,7 +7,7 @@
 //!
 //! - **Project**: Aggregate root containing decomposition and workspace path
 //! - **Package**: Flat scope partition (PKG-###)
-//! - **Deliverable**: Primary work unit with 6-state lifecycle (DEL-##.##)
+//! - **Deliverable**: Primary work unit with a 6-state lifecycle plus hold and descope (DEL-##.##)
 //! - **Document**: The four documents + metadata files
 //! - **AgentSession**: Type 1 (PERSONA) or Type 2 (TASK) execution context
 //!
@@ -15,16 +15,40 @@
 //!
 //! - Filesystem IS the state (no hidden database)
 //! - Git provides version control and audit trail
-//! - Agents have explicit write scopes (WriteGuard)
+//! - Agents have explicit write and read scopes (WriteGuard, ReadGuard)
 //! - Human decision rights are sacred
 
 pub mod entities;
 pub mod state_machines;
 pub mod write_guard;
+pub mod read_guard;
 pub mod brief_parser;
+pub mod brief_schema;
+pub mod brief_builder;
+pub mod agent_definition;
+pub mod folder_names;
+pub mod decomposition;
+pub mod scope_partition;
+pub mod status_file;
+pub mod readiness;
+pub mod dependencies;
+pub mod output_verification;
 pub mod error;
+mod path_rules;
 
 pub use entities::*;
 pub use state_machines::*;
 pub use write_guard::*;
+pub use read_guard::*;
+pub use folder_names::*;
+pub use decomposition::*;
+pub use scope_partition::*;
+pub use agent_definition::*;
+pub use brief_schema::*;
+pub use brief_builder::*;
+pub use status_file::*;
+pub use readiness::*;
+pub use dependencies::*;
+pub use output_verification::*;
 pub use error::DomainError;
+pub use path_rules::resolve_path;
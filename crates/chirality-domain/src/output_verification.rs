@@ -0,0 +1,235 @@
+//! Output-contract verification for finished TASK sessions.
+//!
+//! The executor's own `success` flag is not trusted on its own: after a TASK
+//! session, every `output_contract` path must exist inside the write scope,
+//! reported outputs must match what is on disk, and success criteria that
+//! can be checked mechanically are checked. Criteria use these forms
+//! (case-insensitive, paths relative to the session's base folder):
+//!
+//! - `exists: Datasheet.md`
+//! - `no placeholders` (every contracted output) or `no placeholders: Datasheet.md`
+//! - `section "Loads" in Datasheet.md`
+//!
+//! Anything else is reported as unchecked and left to a human reviewer.
+//! Criterion paths that resolve outside the base folder are refused.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::readiness::NoPlaceholders;
+
+/// A success criterion that can be checked against file contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuccessCriterion {
+    FileExists { path: PathBuf },
+    /// `None` means every contracted output.
+    NoPlaceholders { path: Option<PathBuf> },
+    SectionPresent { path: PathBuf, heading: String },
+}
+
+impl SuccessCriterion {
+    /// Recognise a mechanically checkable criterion, or `None` for free text.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let lower = text.to_ascii_lowercase();
+        let rest = |prefix: &str| text[prefix.len()..].trim();
+
+        for prefix in ["file exists:", "exists:"] {
+            if lower.starts_with(prefix) {
+                return Some(SuccessCriterion::FileExists {
+                    path: contract_path(rest(prefix))?,
+                });
+            }
+        }
+        if lower == "no placeholders" {
+            return Some(SuccessCriterion::NoPlaceholders { path: None });
+        }
+        if lower.starts_with("no placeholders:") {
+            return Some(SuccessCriterion::NoPlaceholders {
+                path: Some(contract_path(rest("no placeholders:"))?),
+            });
+        }
+        if lower.starts_with("section ") {
+            let (heading, path) = rest("section ").strip_prefix('"')?.split_once('"')?;
+            let path = path.trim().strip_prefix("in ")?;
+            return Some(SuccessCriterion::SectionPresent {
+                path: contract_path(path)?,
+                heading: heading.trim().to_string(),
+            });
+        }
+        None
+    }
+
+    /// Check the criterion against the contents of `path`, if it exists.
+    ///
+    /// Returns a message describing the failure, if any.
+    pub fn check(&self, path: &Path, content: Option<&str>) -> Option<String> {
+        let Some(content) = content else {
+            return Some(format!("{} does not exist", path.display()));
+        };
+        match self {
+            SuccessCriterion::FileExists { .. } => None,
+            SuccessCriterion::NoPlaceholders { .. } => content
+                .lines()
+                .enumerate()
+                .find_map(|(index, line)| {
+                    NoPlaceholders::marker_in(line).map(|marker| {
+                        format!("{}:{}: placeholder {:?}", path.display(), index + 1, marker)
+                    })
+                }),
+            SuccessCriterion::SectionPresent { heading, .. } => {
+                let present = content.lines().any(|line| {
+                    let line = line.trim();
+                    line.starts_with('#')
+                        && line.trim_start_matches('#').trim().eq_ignore_ascii_case(heading)
+                });
+                (!present).then(|| format!("{} has no section {:?}", path.display(), heading))
+            }
+        }
+    }
+
+    /// Files this criterion reads, relative to the session's base folder.
+    pub fn paths<'a>(&'a self, contract: &'a [PathBuf]) -> Vec<&'a Path> {
+        match self {
+            SuccessCriterion::FileExists { path }
+            | SuccessCriterion::NoPlaceholders { path: Some(path) }
+            | SuccessCriterion::SectionPresent { path, .. } => vec![path.as_path()],
+            SuccessCriterion::NoPlaceholders { path: None } => {
+                contract.iter().map(PathBuf::as_path).collect()
+            }
+        }
+    }
+}
+
+/// A path named in an output contract or criterion, with backticks removed.
+pub fn contract_path(entry: &str) -> Option<PathBuf> {
+    let entry = entry.trim().trim_matches('`').trim();
+    (!entry.is_empty()).then(|| PathBuf::from(entry))
+}
+
+/// Remove `.` and `..` segments without touching the filesystem.
+///
+/// `..` at the root stays at the root, as the OS does.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// `path` joined onto `base` and normalized, or `None` if it leaves `base`.
+pub fn resolve_under(base: &Path, path: &Path) -> Option<PathBuf> {
+    let base = normalize_path(base);
+    let resolved = normalize_path(&base.join(path));
+    resolved.starts_with(&base).then_some(resolved)
+}
+
+/// A single way a session fell short of its contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationFinding {
+    /// Which verification step reported it (e.g. `output-contract`).
+    pub check: &'static str,
+    pub message: String,
+}
+
+impl VerificationFinding {
+    pub fn new(check: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            message: message.into(),
+        }
+    }
+}
+
+/// Outcome of verifying a TASK session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    pub findings: Vec<VerificationFinding>,
+    /// Success criteria that are not mechanically checkable.
+    pub unchecked_criteria: Vec<String>,
+}
+
+impl VerificationReport {
+    pub fn is_satisfied(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// One line per finding, e.g. `output-contract: Datasheet.md was not produced`.
+    pub fn summary(&self) -> String {
+        self.findings
+            .iter()
+            .map(|f| format!("{}: {}", f.check, f.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_checkable_criteria_and_ignores_prose() {
+        assert_eq!(
+            SuccessCriterion::parse("Exists: `calcs/loads.xlsx`"),
+            Some(SuccessCriterion::FileExists {
+                path: PathBuf::from("calcs/loads.xlsx")
+            })
+        );
+        assert_eq!(
+            SuccessCriterion::parse("no placeholders"),
+            Some(SuccessCriterion::NoPlaceholders { path: None })
+        );
+        assert_eq!(
+            SuccessCriterion::parse("Section \"Design Loads\" in Datasheet.md"),
+            Some(SuccessCriterion::SectionPresent {
+                path: PathBuf::from("Datasheet.md"),
+                heading: "Design Loads".to_string(),
+            })
+        );
+        assert_eq!(SuccessCriterion::parse("Documents are consistent"), None);
+        assert_eq!(SuccessCriterion::parse("exists:"), None);
+    }
+
+    #[test]
+    fn checks_contents() {
+        let path = Path::new("Datasheet.md");
+        let section = SuccessCriterion::parse("section \"loads\" in Datasheet.md").unwrap();
+        assert_eq!(section.check(path, Some("# Datasheet\n## Loads\n")), None);
+        assert!(section.check(path, Some("# Datasheet\nLoads: 5 kN\n")).is_some());
+
+        let placeholders = SuccessCriterion::parse("no placeholders").unwrap();
+        assert_eq!(
+            placeholders.check(path, Some("# Datasheet\nLoad: TBD\n")).as_deref(),
+            Some("Datasheet.md:2: placeholder \"TBD\"")
+        );
+        assert_eq!(
+            placeholders.check(path, None).as_deref(),
+            Some("Datasheet.md does not exist")
+        );
+    }
+
+    #[test]
+    fn resolves_paths_under_the_base_folder() {
+        let base = Path::new("/proj/PKG-001/./DEL-01.01");
+        assert_eq!(
+            resolve_under(base, Path::new("./calcs/../Datasheet.md")),
+            Some(PathBuf::from("/proj/PKG-001/DEL-01.01/Datasheet.md"))
+        );
+        assert_eq!(
+            resolve_under(base, Path::new("/proj/PKG-001/DEL-01.01/Guidance.md")),
+            Some(PathBuf::from("/proj/PKG-001/DEL-01.01/Guidance.md"))
+        );
+        assert_eq!(resolve_under(base, Path::new("../../x")), None);
+        assert_eq!(resolve_under(base, Path::new("/etc/passwd")), None);
+        assert_eq!(resolve_under(base, Path::new("../DEL-01.01x/a.md")), None);
+    }
+}
@@ -0,0 +1,98 @@
+//! Path resolution and matching shared by the read and write guards.
+//!
+//! Every comparison resolves both sides first, so `..` segments and
+//! symlinks cannot be used to step outside a scope.
+
+use std::path::{Component, Path, PathBuf};
+
+/// Check a path under `root` against a glob pattern.
+///
+/// Both sides are resolved first, so `..` and symlinks cannot leave `root`.
+pub(crate) fn glob_allows(root: &Path, pattern: &str, target: &Path) -> bool {
+    let (Some(root), Some(target)) = (resolve_path(root), resolve_path(target)) else {
+        return false;
+    };
+    let Ok(relative) = target.strip_prefix(&root) else {
+        return false;
+    };
+    let segments: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let pattern: Vec<&str> = pattern.split('/').filter(|p| !p.is_empty()).collect();
+    glob_segments(&pattern, &segments)
+}
+
+/// Check if child is within parent directory, after resolving both.
+pub(crate) fn is_within(child: &Path, parent: &Path) -> bool {
+    match (resolve_path(child), resolve_path(parent)) {
+        (Some(c), Some(p)) => c.starts_with(&p),
+        _ => false,
+    }
+}
+
+/// Check if two paths name the same file, after resolving both.
+pub(crate) fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (resolve_path(a), resolve_path(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Resolve a path that may not exist yet.
+///
+/// Components are applied one at a time and every prefix that exists is
+/// canonicalised (following symlinks) before the next one is applied, so a
+/// `..` can never be used to step past a link unresolved
+/// (`missing/../link/x`). Returns `None` for paths that climb above the root
+/// or pass through a dangling symlink, whose eventual target cannot be known.
+pub fn resolve_path(path: &Path) -> Option<PathBuf> {
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() || resolved.as_os_str().is_empty() {
+                    return None;
+                }
+            }
+            Component::Normal(part) => {
+                resolved.push(part);
+                if std::fs::symlink_metadata(&resolved).is_ok() {
+                    resolved = resolved.canonicalize().ok()?;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                resolved.push(component);
+                if let Ok(canonical) = resolved.canonicalize() {
+                    resolved = canonical;
+                }
+            }
+        }
+    }
+    Some(resolved)
+}
+
+fn glob_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| glob_segments(rest, &path[skip..])),
+        Some((first, rest)) => match path.split_first() {
+            Some((segment, path_rest)) => {
+                glob_segment(first.as_bytes(), segment.as_bytes()) && glob_segments(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Match one path segment against `*` / `?` wildcards.
+fn glob_segment(pattern: &[u8], segment: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => segment.is_empty(),
+        Some((b'*', rest)) => (0..=segment.len()).any(|skip| glob_segment(rest, &segment[skip..])),
+        Some((b'?', rest)) => !segment.is_empty() && glob_segment(rest, &segment[1..]),
+        Some((c, rest)) => segment.first() == Some(c) && glob_segment(rest, &segment[1..]),
+    }
+}
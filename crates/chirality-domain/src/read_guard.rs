@@ -0,0 +1,193 @@
+//! Read scope enforcement for agent sessions.
+//!
+//! Mirrors `WriteGuard`: every file an agent reads, whether assembled into
+//! its context or requested through a tool, is checked against the
+//! session's declared read scope.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::DomainError;
+use crate::path_rules;
+
+/// Read scope for an agent session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type")]
+pub enum ReadScope {
+    /// No restriction (the behaviour before read scopes existed)
+    #[default]
+    Unrestricted,
+    /// No reads at all
+    None,
+    /// Anything under a directory (e.g., the workspace root)
+    Within { root_path: PathBuf },
+    /// Only the listed files
+    Files { allowed_files: Vec<PathBuf> },
+    /// Paths under `root` matching a glob (same syntax as `WriteScope::Glob`)
+    Glob { root: PathBuf, pattern: String },
+    /// Any of `allow`, except paths matched by a `deny` rule (deny wins).
+    ///
+    /// e.g. the workspace root, denying `**/Commercial/**` and `HR/**`.
+    Composite {
+        allow: Vec<ReadScope>,
+        #[serde(default)]
+        deny: Vec<ReadScope>,
+    },
+}
+
+impl ReadScope {
+    /// Short description used in violations (e.g. `Within(/project)`).
+    pub fn describe(&self) -> String {
+        match self {
+            ReadScope::Unrestricted => "Unrestricted".to_string(),
+            ReadScope::None => "None".to_string(),
+            ReadScope::Within { root_path } => format!("Within({})", root_path.display()),
+            ReadScope::Files { allowed_files } => format!("Files({:?})", allowed_files),
+            ReadScope::Glob { root, pattern } => format!("Glob({})", root.join(pattern).display()),
+            ReadScope::Composite { allow, deny } => {
+                let list = |rules: &[ReadScope]| {
+                    rules.iter().map(|r| r.describe()).collect::<Vec<_>>().join(", ")
+                };
+                format!("Composite(allow: [{}], deny: [{}])", list(allow), list(deny))
+            }
+        }
+    }
+}
+
+/// Result of read validation.
+#[derive(Debug, Clone)]
+pub enum ReadValidation {
+    Allowed,
+    Denied(ReadViolation),
+}
+
+/// Reason for read denial.
+#[derive(Debug, Clone)]
+pub struct ReadViolation {
+    pub target_path: PathBuf,
+    pub scope: String,
+    pub reason: String,
+    /// Deny rule that matched the path, for composite scopes.
+    pub rule: Option<String>,
+}
+
+/// Validates read operations against declared scopes.
+pub struct ReadGuard;
+
+impl ReadGuard {
+    /// Validate that a read of the target path is allowed.
+    pub fn validate_read(scope: &ReadScope, target_path: &Path) -> ReadValidation {
+        let denied = |reason: String, rule: Option<String>| {
+            ReadValidation::Denied(ReadViolation {
+                target_path: target_path.to_path_buf(),
+                scope: scope.describe(),
+                reason,
+                rule,
+            })
+        };
+        match scope {
+            ReadScope::Unrestricted => ReadValidation::Allowed,
+
+            ReadScope::None => denied("Agent has no read permission".to_string(), None),
+
+            ReadScope::Within { root_path } => {
+                if path_rules::is_within(target_path, root_path) {
+                    ReadValidation::Allowed
+                } else {
+                    denied(format!("Path is outside {}", root_path.display()), None)
+                }
+            }
+
+            ReadScope::Files { allowed_files } => {
+                if allowed_files
+                    .iter()
+                    .any(|f| path_rules::is_same_file(target_path, f))
+                {
+                    ReadValidation::Allowed
+                } else {
+                    denied("Path is not in allowed files".to_string(), None)
+                }
+            }
+
+            ReadScope::Glob { root, pattern } => {
+                if path_rules::glob_allows(root, pattern, target_path) {
+                    ReadValidation::Allowed
+                } else {
+                    denied(
+                        format!("Path does not match {} under {}", pattern, root.display()),
+                        None,
+                    )
+                }
+            }
+
+            ReadScope::Composite { allow, deny } => {
+                let allows = |rule: &ReadScope| {
+                    matches!(Self::validate_read(rule, target_path), ReadValidation::Allowed)
+                };
+                if let Some(rule) = deny.iter().find(|r| allows(r)) {
+                    denied(
+                        format!("Path matches deny rule {}", rule.describe()),
+                        Some(rule.describe()),
+                    )
+                } else if allow.iter().any(allows) {
+                    ReadValidation::Allowed
+                } else {
+                    denied("Path matches no allow rule".to_string(), None)
+                }
+            }
+        }
+    }
+
+    /// Ensure a read is allowed, returning an error if not.
+    pub fn ensure_allowed(scope: &ReadScope, target_path: &Path) -> Result<(), DomainError> {
+        match Self::validate_read(scope, target_path) {
+            ReadValidation::Allowed => Ok(()),
+            ReadValidation::Denied(violation) => Err(DomainError::ReadViolation {
+                target_path: violation.target_path,
+                scope: violation.scope,
+                reason: violation.reason,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(scope: &ReadScope, path: &str) -> bool {
+        matches!(
+            ReadGuard::validate_read(scope, Path::new(path)),
+            ReadValidation::Allowed
+        )
+    }
+
+    #[test]
+    fn unrestricted_is_the_default() {
+        assert!(allowed(&ReadScope::default(), "/anywhere/at/all.md"));
+        assert!(!allowed(&ReadScope::None, "/project/_COORDINATION.md"));
+    }
+
+    #[test]
+    fn confidential_folders_are_denied_within_workspace() {
+        let scope = ReadScope::Composite {
+            allow: vec![ReadScope::Within {
+                root_path: PathBuf::from("/project"),
+            }],
+            deny: vec![ReadScope::Glob {
+                root: PathBuf::from("/project"),
+                pattern: "**/Commercial/**".to_string(),
+            }],
+        };
+
+        assert!(allowed(&scope, "/project/PKG-01/DEL-01.01/Datasheet.md"));
+        assert!(!allowed(&scope, "/other/notes.md"));
+        assert!(!allowed(&scope, "/project/PKG-01/DEL-01.01/../../Commercial/rates.xlsx"));
+        match ReadGuard::validate_read(&scope, Path::new("/project/PKG-01/Commercial/bid.md")) {
+            ReadValidation::Denied(violation) => {
+                assert_eq!(violation.rule.as_deref(), Some("Glob(/project/**/Commercial/**)"));
+            }
+            ReadValidation::Allowed => panic!("deny rule should take precedence"),
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Readiness checks run before a deliverable moves to CHECKING.
+//!
+//! Checks are pure: the caller reads the deliverable folder and hands the
+//! contents over as a `ReadinessInput`. Each `ReadinessCheck` reports
+//! findings; any finding blocks the transition.
+
+use std::path::{Path, PathBuf};
+
+use crate::dependencies::Dependency;
+use crate::entities::{Deliverable, DocumentType};
+
+/// What a readiness check gets to look at.
+#[derive(Debug, Clone)]
+pub struct ReadinessInput<'a> {
+    pub deliverable: &'a Deliverable,
+    /// Contents of the documents present in the deliverable folder.
+    pub documents: Vec<(DocumentType, String)>,
+    /// Files present in the deliverable folder, relative to it.
+    pub files: Vec<PathBuf>,
+}
+
+impl<'a> ReadinessInput<'a> {
+    pub fn new(deliverable: &'a Deliverable) -> Self {
+        Self {
+            deliverable,
+            documents: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    pub fn with_document(mut self, document_type: DocumentType, content: impl Into<String>) -> Self {
+        self.documents.push((document_type, content.into()));
+        self
+    }
+
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push(path.into());
+        self
+    }
+
+    /// Content of a document, if it is present.
+    pub fn document(&self, document_type: DocumentType) -> Option<&str> {
+        self.documents
+            .iter()
+            .find(|(t, _)| *t == document_type)
+            .map(|(_, content)| content.as_str())
+    }
+}
+
+/// A single problem found by a readiness check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadinessFinding {
+    /// Name of the check that reported it.
+    pub check: &'static str,
+    pub message: String,
+    pub document: Option<DocumentType>,
+    /// 1-based line within `document`, when known.
+    pub line: Option<usize>,
+}
+
+impl ReadinessFinding {
+    pub fn new(check: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            message: message.into(),
+            document: None,
+            line: None,
+        }
+    }
+
+    pub fn at(mut self, document: DocumentType, line: Option<usize>) -> Self {
+        self.document = Some(document);
+        self.line = line;
+        self
+    }
+}
+
+/// Outcome of running a set of readiness checks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadinessReport {
+    /// Names of the checks that ran, in order.
+    pub checks_run: Vec<&'static str>,
+    pub findings: Vec<ReadinessFinding>,
+}
+
+impl ReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// One line per finding, e.g. `no-placeholders: Datasheet.md:12: TBD`.
+    pub fn summary(&self) -> String {
+        self.findings
+            .iter()
+            .map(|f| {
+                let location = match (f.document, f.line) {
+                    (Some(d), Some(line)) => format!("{}:{}: ", d.filename(), line),
+                    (Some(d), None) => format!("{}: ", d.filename()),
+                    _ => String::new(),
+                };
+                format!("{}: {}{}", f.check, location, f.message)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A pluggable readiness check.
+pub trait ReadinessCheck: Send + Sync {
+    /// Short, stable name used in findings (e.g. `core-documents`).
+    fn name(&self) -> &'static str;
+
+    fn check(&self, input: &ReadinessInput<'_>) -> Vec<ReadinessFinding>;
+}
+
+/// An ordered set of readiness checks.
+pub struct ReadinessChecks {
+    checks: Vec<Box<dyn ReadinessCheck>>,
+}
+
+impl ReadinessChecks {
+    /// No checks at all.
+    pub fn empty() -> Self {
+        Self { checks: Vec::new() }
+    }
+
+    /// The built-in checks.
+    pub fn standard() -> Self {
+        Self::empty()
+            .with(CoreDocumentsPresent)
+            .with(NoPlaceholders)
+            .with(DependenciesResolved)
+            .with(AnticipatedArtifactsPresent)
+    }
+
+    pub fn with(mut self, check: impl ReadinessCheck + 'static) -> Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    pub fn run(&self, input: &ReadinessInput<'_>) -> ReadinessReport {
+        let mut report = ReadinessReport::default();
+        for check in &self.checks {
+            report.checks_run.push(check.name());
+            report.findings.extend(check.check(input));
+        }
+        report
+    }
+}
+
+impl Default for ReadinessChecks {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// All four core documents exist and are not empty.
+pub struct CoreDocumentsPresent;
+
+impl ReadinessCheck for CoreDocumentsPresent {
+    fn name(&self) -> &'static str {
+        "core-documents"
+    }
+
+    fn check(&self, input: &ReadinessInput<'_>) -> Vec<ReadinessFinding> {
+        DocumentType::ALL
+            .into_iter()
+            .filter(DocumentType::is_core)
+            .filter_map(|t| match input.document(t) {
+                None => Some(ReadinessFinding::new(self.name(), "missing").at(t, None)),
+                Some(content) if content.trim().is_empty() => {
+                    Some(ReadinessFinding::new(self.name(), "empty").at(t, None))
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Core documents carry no `TBD`/`TODO`-style markers or template slots.
+pub struct NoPlaceholders;
+
+impl NoPlaceholders {
+    const WORDS: [&'static str; 4] = ["TBD", "TBC", "TODO", "FIXME"];
+    const FRAGMENTS: [&'static str; 2] = ["{{", "[PLACEHOLDER"];
+
+    /// The first placeholder marker on a line, if any.
+    pub fn marker_in(line: &str) -> Option<&'static str> {
+        let words = line.split(|c: char| !c.is_ascii_alphanumeric());
+        for word in words {
+            if let Some(marker) = Self::WORDS.iter().find(|m| **m == word) {
+                return Some(marker);
+            }
+        }
+        let upper = line.to_ascii_uppercase();
+        Self::FRAGMENTS.into_iter().find(|f| upper.contains(f))
+    }
+}
+
+impl ReadinessCheck for NoPlaceholders {
+    fn name(&self) -> &'static str {
+        "no-placeholders"
+    }
+
+    fn check(&self, input: &ReadinessInput<'_>) -> Vec<ReadinessFinding> {
+        let mut findings = Vec::new();
+        for (document_type, content) in input.documents.iter().filter(|(t, _)| t.is_core()) {
+            for (index, line) in content.lines().enumerate() {
+                if let Some(marker) = Self::marker_in(line) {
+                    findings.push(
+                        ReadinessFinding::new(self.name(), format!("placeholder {:?}", marker))
+                            .at(*document_type, Some(index + 1)),
+                    );
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// Every blocking (hard) dependency in `_DEPENDENCIES.md` is resolved.
+///
+/// Rows are read with `Dependency::parse_file`; soft rows never block. A
+/// deliverable without `_DEPENDENCIES.md` has nothing to resolve.
+pub struct DependenciesResolved;
+
+impl DependenciesResolved {
+    const OPEN_MARKERS: [&'static str; 3] = ["OPEN", "PENDING", "UNRESOLVED"];
+
+    /// Is this `_DEPENDENCIES.md` line an open item?
+    pub fn is_unresolved(line: &str) -> bool {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("- [ ]") || trimmed.starts_with("* [ ]") {
+            return true;
+        }
+        line.split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| Self::OPEN_MARKERS.contains(&word))
+    }
+}
+
+impl ReadinessCheck for DependenciesResolved {
+    fn name(&self) -> &'static str {
+        "dependencies-resolved"
+    }
+
+    fn check(&self, input: &ReadinessInput<'_>) -> Vec<ReadinessFinding> {
+        let Some(content) = input.document(DocumentType::Dependencies) else {
+            return Vec::new();
+        };
+        let mut findings: Vec<ReadinessFinding> = Vec::new();
+        for dependency in Dependency::parse_file(&input.deliverable.id, content) {
+            if !dependency.is_blocking() || dependency.resolved {
+                continue;
+            }
+            // A row naming several deliverables is still one finding.
+            if findings.iter().any(|f| f.line == Some(dependency.line)) {
+                continue;
+            }
+            findings.push(
+                ReadinessFinding::new(self.name(), format!("unresolved: {}", dependency.description))
+                    .at(DocumentType::Dependencies, Some(dependency.line)),
+            );
+        }
+        findings
+    }
+}
+
+/// Every anticipated artifact exists in the deliverable folder.
+///
+/// Artifacts are matched by relative path, or by file name when the
+/// anticipated entry has no directory part.
+pub struct AnticipatedArtifactsPresent;
+
+impl ReadinessCheck for AnticipatedArtifactsPresent {
+    fn name(&self) -> &'static str {
+        "anticipated-artifacts"
+    }
+
+    fn check(&self, input: &ReadinessInput<'_>) -> Vec<ReadinessFinding> {
+        input
+            .deliverable
+            .anticipated_artifacts
+            .iter()
+            .filter(|artifact| {
+                let wanted = Path::new(artifact.as_str());
+                !input.files.iter().any(|file| {
+                    file == wanted
+                        || (wanted.parent() == Some(Path::new(""))
+                            && file.file_name() == wanted.file_name())
+                })
+            })
+            .map(|artifact| ReadinessFinding::new(self.name(), format!("missing {}", artifact)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PackageId;
+
+    fn deliverable() -> Deliverable {
+        let mut deliverable =
+            Deliverable::new(PackageId::from_legacy(1), "Foundation", PathBuf::from("DEL-01.01"));
+        deliverable.anticipated_artifacts = vec!["calcs/loads.xlsx".into(), "layout.dwg".into()];
+        deliverable
+    }
+
+    fn complete(deliverable: &Deliverable) -> ReadinessInput<'_> {
+        DocumentType::ALL
+            .into_iter()
+            .filter(DocumentType::is_core)
+            .fold(ReadinessInput::new(deliverable), |input, t| {
+                input.with_document(t, "# Done\n")
+            })
+            .with_document(DocumentType::Dependencies, "- [x] Survey (RESOLVED)\n")
+            .with_file("calcs/loads.xlsx")
+            .with_file("drawings/layout.dwg")
+    }
+
+    #[test]
+    fn complete_deliverable_is_ready() {
+        let deliverable = deliverable();
+        let report = ReadinessChecks::standard().run(&complete(&deliverable));
+        assert!(report.is_ready(), "{}", report.summary());
+        assert_eq!(report.checks_run.len(), 4);
+    }
+
+    #[test]
+    fn reports_each_problem_with_location() {
+        let deliverable = deliverable();
+        let input = ReadinessInput::new(&deliverable)
+            .with_document(DocumentType::Datasheet, "# Datasheet\nLoad: TBD kN\n")
+            .with_document(DocumentType::Specification, "Notes: {{notes}}\n")
+            .with_document(DocumentType::Guidance, "# Guidance\n")
+            .with_document(
+                DocumentType::Dependencies,
+                "- [ ] Geotech report\n| Soil data | OPEN |\n- [x] Survey\n",
+            )
+            .with_file("calcs/loads.xlsx");
+
+        let report = ReadinessChecks::standard().run(&input);
+        let by_check = |name| report.findings.iter().filter(|f| f.check == name).count();
+        assert_eq!(by_check("core-documents"), 1);
+        assert_eq!(by_check("no-placeholders"), 2);
+        assert_eq!(by_check("dependencies-resolved"), 2);
+        assert_eq!(by_check("anticipated-artifacts"), 1);
+        assert!(report
+            .summary()
+            .contains("no-placeholders: Datasheet.md:2: placeholder \"TBD\""));
+    }
+
+    #[test]
+    fn only_open_hard_dependencies_block() {
+        let deliverable = deliverable();
+        let mut input = complete(&deliverable);
+        input.documents.retain(|(t, _)| *t != DocumentType::Dependencies);
+        let input = input.with_document(
+            DocumentType::Dependencies,
+            "# Dependencies\n\nOPEN questions are tracked below.\n\n             - [ ] DEL-02.01 Column loads (soft)\n             - [ ] DEL-01.02 Survey, DEL-01.03 Borehole logs\n             - [x] DEL-01.04 Drainage\n",
+        );
+        let findings = DependenciesResolved.check(&input);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, Some(6));
+        assert_eq!(findings[0].message, "unresolved: DEL-01.02 Survey, DEL-01.03 Borehole logs");
+    }
+
+    #[test]
+    fn placeholder_words_must_stand_alone() {
+        assert_eq!(NoPlaceholders::marker_in("TBDs are gone, STODO"), None);
+        assert_eq!(NoPlaceholders::marker_in("value (TBC)"), Some("TBC"));
+    }
+}
@@ -0,0 +1,180 @@
+//! Package scope partition invariants.
+//!
+//! From chirality-app: packages are non-overlapping, non-nested scope
+//! partitions, and every scope item from the SOW maps to exactly one package.
+//! Scope items are compared case-insensitively with whitespace collapsed, so
+//! `Site  preparation` and `site preparation` are the same item.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::entities::{Package, PackageId};
+use crate::error::DomainError;
+
+/// One way a set of packages fails to partition the scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionIssue {
+    /// The same scope item is assigned to more than one package.
+    DuplicateScopeItem { item: String, packages: Vec<PackageId> },
+    /// A SOW item no package covers.
+    UnassignedScopeItem { item: String },
+    /// A package scope item that is not in the SOW.
+    UnknownScopeItem { item: String, package_id: PackageId },
+    /// A package with no scope items.
+    EmptyPackage { package_id: PackageId },
+}
+
+impl fmt::Display for PartitionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartitionIssue::DuplicateScopeItem { item, packages } => {
+                let packages: Vec<String> = packages.iter().map(|p| p.to_string()).collect();
+                write!(f, "{:?} is assigned to {}", item, packages.join(", "))
+            }
+            PartitionIssue::UnassignedScopeItem { item } => {
+                write!(f, "{:?} is not assigned to any package", item)
+            }
+            PartitionIssue::UnknownScopeItem { item, package_id } => {
+                write!(f, "{:?} in {} is not in the SOW", item, package_id)
+            }
+            PartitionIssue::EmptyPackage { package_id } => {
+                write!(f, "{} has no scope items", package_id)
+            }
+        }
+    }
+}
+
+/// Checks that packages partition a project's scope.
+pub struct ScopePartition;
+
+impl ScopePartition {
+    /// Every partition issue: empty packages (in package order), then
+    /// duplicated and unassigned items (each sorted by normalized item
+    /// text), then unknown items (in package order).
+    ///
+    /// With an empty `sow_items` list only duplicates and empty packages are
+    /// reported, since there is nothing to check coverage against.
+    pub fn issues(packages: &[Package], sow_items: &[String]) -> Vec<PartitionIssue> {
+        let mut issues = Vec::new();
+        let mut owners: BTreeMap<String, (String, Vec<PackageId>)> = BTreeMap::new();
+        for package in packages {
+            if package.scope_items.iter().all(|i| i.trim().is_empty()) {
+                issues.push(PartitionIssue::EmptyPackage {
+                    package_id: package.id.clone(),
+                });
+            }
+            for item in package.scope_items.iter().filter(|i| !i.trim().is_empty()) {
+                let (_, owned_by) = owners
+                    .entry(normalize(item))
+                    .or_insert_with(|| (item.trim().to_string(), Vec::new()));
+                if !owned_by.contains(&package.id) {
+                    owned_by.push(package.id.clone());
+                }
+            }
+        }
+
+        for (item, owned_by) in owners.values() {
+            if owned_by.len() > 1 {
+                issues.push(PartitionIssue::DuplicateScopeItem {
+                    item: item.clone(),
+                    packages: owned_by.clone(),
+                });
+            }
+        }
+        if sow_items.is_empty() {
+            return issues;
+        }
+
+        let sow: BTreeMap<String, &String> = sow_items.iter().map(|i| (normalize(i), i)).collect();
+        for (key, item) in &sow {
+            if !owners.contains_key(key) {
+                issues.push(PartitionIssue::UnassignedScopeItem {
+                    item: item.trim().to_string(),
+                });
+            }
+        }
+        for package in packages {
+            for item in package.scope_items.iter().filter(|i| !i.trim().is_empty()) {
+                if !sow.contains_key(&normalize(item)) {
+                    issues.push(PartitionIssue::UnknownScopeItem {
+                        item: item.trim().to_string(),
+                        package_id: package.id.clone(),
+                    });
+                }
+            }
+        }
+        issues
+    }
+
+    /// Fail with every issue found, if there are any.
+    pub fn validate(packages: &[Package], sow_items: &[String]) -> Result<(), DomainError> {
+        let issues = Self::issues(packages, sow_items);
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(DomainError::ScopePartitionViolations { issues })
+        }
+    }
+}
+
+fn normalize(item: &str) -> String {
+    item.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ProjectId;
+
+    fn package(num: u32, items: &[&str]) -> Package {
+        Package::new(ProjectId::from_string("p"), format!("P{}", num))
+            .with_legacy_id(num)
+            .with_scope_items(items.iter().map(|i| i.to_string()).collect())
+    }
+
+    fn sow(items: &[&str]) -> Vec<String> {
+        items.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn clean_partition_passes() {
+        let packages = [package(1, &["Foundations"]), package(2, &["HVAC", "Plumbing"])];
+        assert!(ScopePartition::validate(&packages, &sow(&["foundations", "HVAC", "Plumbing"])).is_ok());
+    }
+
+    #[test]
+    fn reports_every_issue() {
+        let packages = [
+            package(1, &["Foundations", "Site  preparation"]),
+            package(2, &["site preparation", "Landscaping"]),
+            package(3, &[]),
+        ];
+        let issues = ScopePartition::issues(&packages, &sow(&["Foundations", "Site preparation", "HVAC"]));
+        assert_eq!(
+            issues,
+            [
+                PartitionIssue::EmptyPackage {
+                    package_id: PackageId::from_legacy(3)
+                },
+                PartitionIssue::DuplicateScopeItem {
+                    item: "Site  preparation".to_string(),
+                    packages: vec![PackageId::from_legacy(1), PackageId::from_legacy(2)],
+                },
+                PartitionIssue::UnassignedScopeItem {
+                    item: "HVAC".to_string()
+                },
+                PartitionIssue::UnknownScopeItem {
+                    item: "Landscaping".to_string(),
+                    package_id: PackageId::from_legacy(2),
+                },
+            ]
+        );
+        assert!(ScopePartition::validate(&packages, &[])
+            .unwrap_err()
+            .to_string()
+            .contains("\"Site  preparation\" is assigned to PKG-001, PKG-002"));
+    }
+}
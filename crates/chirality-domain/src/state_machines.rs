@@ -1,8 +1,10 @@
 //! State machines for domain entities.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
-use crate::entities::AgentClass;
+use crate::entities::{ActorId, AgentClass};
 use crate::error::DomainError;
 
 /// Deliverable lifecycle state (from chirality-app).
@@ -13,6 +15,10 @@ use crate::error::DomainError;
 ///            └────────────────┴───────────────┘           │
 ///                        (StartWork)                      │
 ///                                         (Reject) ◄──────┘
+///
+/// Any state before ISSUED  ──(Hold)──►  ON_HOLD  ──(Resume)──►  prior state
+/// Any state before ISSUED, or ON_HOLD  ──►  CANCELLED | SUPERSEDED
+/// ISSUED  ──►  SUPERSEDED
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -30,39 +36,115 @@ pub enum DeliverableState {
     Checking,
     /// Released (terminal)
     Issued,
+    /// Suspended; resumes to the state it was held from
+    OnHold,
+    /// Descoped (terminal)
+    Cancelled,
+    /// Replaced by another deliverable (terminal)
+    Superseded,
 }
 
 impl DeliverableState {
     /// Check if transition to target state is valid.
+    ///
+    /// Resuming from ON_HOLD is accepted towards any holdable state here;
+    /// `Deliverable::transition` narrows it to the state it was held from.
     pub fn can_transition_to(&self, target: &DeliverableState) -> bool {
+        match (self, target) {
+            (DeliverableState::Open, DeliverableState::Initialized)
+            | (DeliverableState::Initialized, DeliverableState::SemanticReady)
+            | (DeliverableState::Initialized, DeliverableState::InProgress)
+            | (DeliverableState::SemanticReady, DeliverableState::InProgress)
+            | (DeliverableState::InProgress, DeliverableState::Checking)
+            | (DeliverableState::Checking, DeliverableState::InProgress) // Reject
+            | (DeliverableState::Checking, DeliverableState::Issued)
+            | (DeliverableState::Issued, DeliverableState::Superseded) => true,
+
+            // Hold and resume
+            (from, DeliverableState::OnHold) => from.is_holdable(),
+            (DeliverableState::OnHold, to) if to.is_holdable() => true,
+
+            // Descope
+            (from, DeliverableState::Cancelled | DeliverableState::Superseded) => {
+                from.is_holdable() || *from == DeliverableState::OnHold
+            }
+
+            _ => false,
+        }
+    }
+
+    /// Is this transition reserved for a human decision?
+    ///
+    /// Issuing and rejecting a deliverable under review, holding, resuming
+    /// and descoping are human decision rights; agents and the system may
+    /// only propose them.
+    pub fn requires_human(&self, target: &DeliverableState) -> bool {
         matches!(
             (self, target),
-            (DeliverableState::Open, DeliverableState::Initialized)
-                | (DeliverableState::Initialized, DeliverableState::SemanticReady)
-                | (DeliverableState::Initialized, DeliverableState::InProgress)
-                | (DeliverableState::SemanticReady, DeliverableState::InProgress)
-                | (DeliverableState::InProgress, DeliverableState::Checking)
-                | (DeliverableState::Checking, DeliverableState::InProgress) // Reject
-                | (DeliverableState::Checking, DeliverableState::Issued)
+            (DeliverableState::Checking, DeliverableState::Issued)
+                | (DeliverableState::Checking, DeliverableState::InProgress)
+                | (DeliverableState::OnHold, _)
+                | (_, DeliverableState::OnHold)
+                | (_, DeliverableState::Cancelled)
+                | (_, DeliverableState::Superseded)
         )
     }
 
-    /// Attempt to transition to a new state.
-    pub fn transition_to(self, target: DeliverableState) -> Result<DeliverableState, DomainError> {
-        if self.can_transition_to(&target) {
-            Ok(target)
-        } else {
-            Err(DomainError::InvalidStateTransition {
+    /// Does entering this state require a recorded reason?
+    pub fn requires_reason(&self) -> bool {
+        matches!(
+            self,
+            DeliverableState::OnHold | DeliverableState::Cancelled | DeliverableState::Superseded
+        )
+    }
+
+    /// Attempt to transition to a new state on behalf of an actor.
+    pub fn transition_to(
+        self,
+        target: DeliverableState,
+        actor: &ActorId,
+    ) -> Result<DeliverableState, DomainError> {
+        if !self.can_transition_to(&target) {
+            return Err(DomainError::InvalidStateTransition {
                 entity: "Deliverable".to_string(),
                 from: format!("{:?}", self),
                 to: format!("{:?}", target),
-            })
+            });
         }
+        if self.requires_human(&target) && !actor.is_human() {
+            return Err(DomainError::HumanActorRequired {
+                operation: format!("Deliverable {} -> {} (requested by {})", self, target, actor),
+            });
+        }
+        Ok(target)
     }
 
     /// Is this a terminal state?
     pub fn is_terminal(&self) -> bool {
-        matches!(self, DeliverableState::Issued)
+        matches!(
+            self,
+            DeliverableState::Issued | DeliverableState::Cancelled | DeliverableState::Superseded
+        )
+    }
+
+    /// Can a deliverable in this state be put on hold (and resumed into it)?
+    pub fn is_holdable(&self) -> bool {
+        matches!(
+            self,
+            DeliverableState::Open
+                | DeliverableState::Initialized
+                | DeliverableState::SemanticReady
+                | DeliverableState::InProgress
+                | DeliverableState::Checking
+        )
+    }
+
+    /// May agent sessions run against a deliverable in this state?
+    pub fn accepts_sessions(&self) -> bool {
+        !matches!(
+            self,
+            DeliverableState::OnHold | DeliverableState::Cancelled | DeliverableState::Superseded
+        )
     }
 
     /// Can work be done in this state?
@@ -74,6 +156,50 @@ impl DeliverableState {
                 | DeliverableState::InProgress
         )
     }
+
+    /// Canonical name as written in `_STATUS.md` (e.g. `IN_PROGRESS`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliverableState::Open => "OPEN",
+            DeliverableState::Initialized => "INITIALIZED",
+            DeliverableState::SemanticReady => "SEMANTIC_READY",
+            DeliverableState::InProgress => "IN_PROGRESS",
+            DeliverableState::Checking => "CHECKING",
+            DeliverableState::Issued => "ISSUED",
+            DeliverableState::OnHold => "ON_HOLD",
+            DeliverableState::Cancelled => "CANCELLED",
+            DeliverableState::Superseded => "SUPERSEDED",
+        }
+    }
+}
+
+impl fmt::Display for DeliverableState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DeliverableState {
+    type Err = DomainError;
+
+    /// Parse a state name, accepting `IN_PROGRESS`, `In Progress` or `in-progress`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_uppercase().replace([' ', '-'], "_");
+        match normalized.as_str() {
+            "OPEN" => Ok(DeliverableState::Open),
+            "INITIALIZED" => Ok(DeliverableState::Initialized),
+            "SEMANTIC_READY" => Ok(DeliverableState::SemanticReady),
+            "IN_PROGRESS" => Ok(DeliverableState::InProgress),
+            "CHECKING" => Ok(DeliverableState::Checking),
+            "ISSUED" => Ok(DeliverableState::Issued),
+            "ON_HOLD" => Ok(DeliverableState::OnHold),
+            "CANCELLED" => Ok(DeliverableState::Cancelled),
+            "SUPERSEDED" => Ok(DeliverableState::Superseded),
+            _ => Err(DomainError::InvalidState {
+                message: format!("unknown deliverable state: {:?}", s),
+            }),
+        }
+    }
 }
 
 /// Agent session lifecycle state.
@@ -161,14 +287,30 @@ mod tests {
 
     #[test]
     fn deliverable_lifecycle_happy_path() {
+        let agent = ActorId::agent("4_DOCUMENTS");
+        let human = ActorId::human("alice");
         let state = DeliverableState::Open;
-        let state = state.transition_to(DeliverableState::Initialized).unwrap();
-        let state = state.transition_to(DeliverableState::InProgress).unwrap();
-        let state = state.transition_to(DeliverableState::Checking).unwrap();
-        let state = state.transition_to(DeliverableState::Issued).unwrap();
+        let state = state.transition_to(DeliverableState::Initialized, &agent).unwrap();
+        let state = state.transition_to(DeliverableState::InProgress, &human).unwrap();
+        let state = state.transition_to(DeliverableState::Checking, &human).unwrap();
+        let state = state.transition_to(DeliverableState::Issued, &human).unwrap();
         assert!(state.is_terminal());
     }
 
+    #[test]
+    fn only_humans_issue_or_reject() {
+        let agent = ActorId::agent("4_DOCUMENTS");
+        for target in [DeliverableState::Issued, DeliverableState::InProgress] {
+            let result = DeliverableState::Checking.transition_to(target, &agent);
+            assert!(matches!(result, Err(DomainError::HumanActorRequired { .. })));
+            let result = DeliverableState::Checking.transition_to(target, &ActorId::system());
+            assert!(matches!(result, Err(DomainError::HumanActorRequired { .. })));
+        }
+        assert!(DeliverableState::InProgress
+            .transition_to(DeliverableState::Checking, &agent)
+            .is_ok());
+    }
+
     #[test]
     fn deliverable_can_skip_semantic() {
         let state = DeliverableState::Initialized;
@@ -181,6 +323,27 @@ mod tests {
         assert!(state.can_transition_to(&DeliverableState::InProgress));
     }
 
+    #[test]
+    fn hold_resume_and_descope_are_human_only() {
+        let agent = ActorId::agent("4_DOCUMENTS");
+        let human = ActorId::human("alice");
+        let held = DeliverableState::Checking
+            .transition_to(DeliverableState::OnHold, &human)
+            .unwrap();
+        assert!(!held.allows_work());
+        assert!(!held.accepts_sessions());
+        assert!(matches!(
+            held.transition_to(DeliverableState::Checking, &agent),
+            Err(DomainError::HumanActorRequired { .. })
+        ));
+        assert!(held.can_transition_to(&DeliverableState::Cancelled));
+        assert!(!held.can_transition_to(&DeliverableState::Issued));
+        assert!(!DeliverableState::Issued.can_transition_to(&DeliverableState::OnHold));
+        assert!(DeliverableState::Issued.can_transition_to(&DeliverableState::Superseded));
+        assert!(DeliverableState::Cancelled.is_terminal());
+        assert_eq!("on hold".parse::<DeliverableState>().unwrap(), DeliverableState::OnHold);
+    }
+
     #[test]
     fn task_session_cannot_pause() {
         let state = SessionState::Active;
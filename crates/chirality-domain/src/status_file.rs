@@ -0,0 +1,789 @@
+//! `_STATUS.md` persistence for deliverable state.
+//!
+//! From chirality-app: `_STATUS.md` is the human-readable record of where a
+//! deliverable is in its lifecycle. The runtime owns a single fenced block
+//! inside the file and leaves everything else (headings, notes, tables)
+//! exactly as the humans wrote it:
+//!
+//! ```text
+//! # Status
+//!
+//! <!-- chirality:status:begin -->
+//! - **State:** IN_PROGRESS
+//! - **Revision:** B
+//! - **Last Transition:** INITIALIZED -> IN_PROGRESS
+//! - **Actor:** HUMAN:alice
+//! - **Timestamp:** 2026-01-05T09:30:00Z
+//! - **History:**
+//!   - 2026-01-02T08:00:00Z | OPEN -> INITIALIZED | AGENT:4_DOCUMENTS
+//!   - 2026-01-05T09:30:00Z | INITIALIZED -> IN_PROGRESS | HUMAN:alice | Drafts accepted
+//! - **Issued Revisions:**
+//!   - A | 2025-11-20T16:00:00Z | HUMAN:alice | Datasheet.md=sha256:…; Specification.md=sha256:…
+//! - **Needs Revisit:**
+//!   - 2026-01-06T10:00:00Z | DEL-01.02 | depends on DEL-01.02, which changed (Datasheet.md)
+//! <!-- chirality:status:end -->
+//!
+//! ## Notes
+//! ...
+//! ```
+//!
+//! Reasons and actors are free text; newlines and backslashes in them are
+//! written as `\n`, `\r` and `\\` so every entry stays on one line.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::entities::{
+    ContentHash, DeliverableId, DocumentType, IssuedRevision, Revision, StateTransition,
+};
+use crate::error::DomainError;
+use crate::state_machines::DeliverableState;
+
+const BEGIN_MARKER: &str = "<!-- chirality:status:begin -->";
+const END_MARKER: &str = "<!-- chirality:status:end -->";
+
+/// Parsed `_STATUS.md`: the managed block plus the verbatim surrounding text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusFile {
+    pub state: DeliverableState,
+    pub revision: Option<Revision>,
+    pub last_transition: Option<StateTransition>,
+    /// Append-only transition history, oldest first.
+    pub history: Vec<StateTransition>,
+    /// Frozen records of previously issued revisions, oldest first.
+    pub issued_revisions: Vec<IssuedRevision>,
+    /// Upstream changes a human should look at, oldest first.
+    pub needs_revisit: Vec<RevisitNote>,
+    /// Keys inside the managed block the runtime does not understand, each
+    /// with the known field it followed so render keeps the human's order.
+    extra_fields: Vec<ExtraField>,
+    /// Everything before the managed block, byte for byte.
+    before: String,
+    /// Everything after the managed block, byte for byte.
+    after: String,
+}
+
+impl StatusFile {
+    /// A fresh status file for a newly scaffolded deliverable.
+    pub fn new(state: DeliverableState) -> Self {
+        Self {
+            state,
+            revision: None,
+            last_transition: None,
+            history: Vec::new(),
+            issued_revisions: Vec::new(),
+            needs_revisit: Vec::new(),
+            extra_fields: Vec::new(),
+            before: "# Status\n\n".to_string(),
+            after: "\n## Notes\n".to_string(),
+        }
+    }
+
+    /// Parse `_STATUS.md` content.
+    ///
+    /// A file without a managed block is treated as an OPEN deliverable whose
+    /// entire content is human-authored; the block is added on first render.
+    pub fn parse(content: &str) -> Result<Self, DomainError> {
+        let Some(begin) = find_marker_line(content, BEGIN_MARKER, 0) else {
+            if content.contains(END_MARKER) {
+                return Err(DomainError::InvalidStatusFile {
+                    line: line_number(content, content.find(END_MARKER).unwrap_or(0)),
+                    reason: "end marker without begin marker".to_string(),
+                });
+            }
+            return Ok(Self::without_block(content));
+        };
+        let end = find_marker_line(content, END_MARKER, begin.end).ok_or_else(|| {
+            DomainError::InvalidStatusFile {
+                line: line_number(content, begin.start),
+                reason: "status block is not closed".to_string(),
+            }
+        })?;
+
+        let mut status = Self {
+            state: DeliverableState::Open,
+            revision: None,
+            last_transition: None,
+            history: Vec::new(),
+            issued_revisions: Vec::new(),
+            needs_revisit: Vec::new(),
+            extra_fields: Vec::new(),
+            before: content[..begin.start].to_string(),
+            after: content[end.end..].to_string(),
+        };
+        status.parse_block(content, begin.end, end.start)?;
+        Ok(status)
+    }
+
+    /// Render the file, rewriting only the managed block.
+    pub fn render(&self) -> String {
+        let mut out = String::with_capacity(self.before.len() + self.after.len() + 256);
+        out.push_str(&self.before);
+        out.push_str(BEGIN_MARKER);
+        out.push('\n');
+        self.push_extra_fields(&mut out, None);
+        push_field(&mut out, "State", self.state.as_str());
+        self.push_extra_fields(&mut out, Some("State"));
+        if let Some(revision) = &self.revision {
+            push_field(&mut out, "Revision", revision.as_str());
+        }
+        self.push_extra_fields(&mut out, Some("Revision"));
+        let transition = self.last_transition.as_ref();
+        if let Some(transition) = transition {
+            push_field(
+                &mut out,
+                "Last Transition",
+                &format!("{} -> {}", transition.from, transition.to),
+            );
+        }
+        self.push_extra_fields(&mut out, Some("Last Transition"));
+        if let Some(transition) = transition {
+            push_field(&mut out, "Actor", &escape(&transition.actor.to_string()));
+        }
+        self.push_extra_fields(&mut out, Some("Actor"));
+        if let Some(transition) = transition {
+            push_field(
+                &mut out,
+                "Timestamp",
+                &transition.at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            );
+        }
+        self.push_extra_fields(&mut out, Some("Timestamp"));
+        if let Some(reason) = transition.and_then(|t| t.reason.as_ref()) {
+            push_field(&mut out, "Reason", &escape(reason));
+        }
+        self.push_extra_fields(&mut out, Some("Reason"));
+        if !self.history.is_empty() {
+            out.push_str("- **History:**\n");
+            for entry in &self.history {
+                out.push_str("  - ");
+                out.push_str(&format_history_entry(entry));
+                out.push('\n');
+            }
+        }
+        self.push_extra_fields(&mut out, Some("History"));
+        if !self.issued_revisions.is_empty() {
+            out.push_str("- **Issued Revisions:**\n");
+            for issued in &self.issued_revisions {
+                out.push_str("  - ");
+                out.push_str(&format_issued_revision(issued));
+                out.push('\n');
+            }
+        }
+        self.push_extra_fields(&mut out, Some("Issued Revisions"));
+        if !self.needs_revisit.is_empty() {
+            out.push_str("- **Needs Revisit:**\n");
+            for note in &self.needs_revisit {
+                out.push_str(&format!(
+                    "  - {} | {} | {}\n",
+                    note.at.to_rfc3339_opts(SecondsFormat::Secs, true),
+                    escape_cell(note.upstream.as_str()),
+                    escape(&note.reason)
+                ));
+            }
+        }
+        self.push_extra_fields(&mut out, Some("Needs Revisit"));
+        out.push_str(END_MARKER);
+        out.push('\n');
+        out.push_str(&self.after);
+        out
+    }
+
+    /// Record a state change: update the state and last transition and
+    /// append it to the history.
+    pub fn record_transition(&mut self, transition: StateTransition) {
+        self.state = transition.to;
+        self.history.push(transition.clone());
+        self.last_transition = Some(transition);
+    }
+
+    /// Flag the deliverable for revisit; returns `false` if an identical
+    /// note (same upstream and reason) is already pending.
+    pub fn flag_revisit(&mut self, note: RevisitNote) -> bool {
+        if self
+            .needs_revisit
+            .iter()
+            .any(|n| n.upstream == note.upstream && n.reason == note.reason)
+        {
+            return false;
+        }
+        self.needs_revisit.push(note);
+        true
+    }
+
+    /// Human-authored text following the managed block.
+    pub fn notes(&self) -> &str {
+        &self.after
+    }
+
+    /// Unknown fields that followed the known field `after` (or opened the
+    /// block, for `None`).
+    fn push_extra_fields(&self, out: &mut String, after: Option<&str>) {
+        for extra in self.extra_fields.iter().filter(|e| e.after.as_deref() == after) {
+            push_field(out, &extra.key, &extra.value);
+        }
+    }
+
+    fn without_block(content: &str) -> Self {
+        // Keep a leading title above the block so the file still reads naturally.
+        let split = if content.starts_with("# ") {
+            content.find('\n').map(|i| i + 1).unwrap_or(content.len())
+        } else {
+            0
+        };
+        let mut before = content[..split].to_string();
+        if !before.is_empty() {
+            if !before.ends_with('\n') {
+                before.push('\n');
+            }
+            before.push('\n');
+        }
+        let after = content[split..].trim_start_matches('\n');
+        Self {
+            state: DeliverableState::Open,
+            revision: None,
+            last_transition: None,
+            history: Vec::new(),
+            issued_revisions: Vec::new(),
+            needs_revisit: Vec::new(),
+            extra_fields: Vec::new(),
+            before,
+            after: if after.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", after)
+            },
+        }
+    }
+
+    fn parse_block(&mut self, content: &str, start: usize, end: usize) -> Result<(), DomainError> {
+        let first_line = line_number(content, start);
+        let mut state = None;
+        let mut transition: Option<(DeliverableState, DeliverableState)> = None;
+        let mut actor = None;
+        let mut at = None;
+        let mut reason = None;
+        let mut list: Option<NestedList> = None;
+        let mut previous_known: Option<String> = None;
+
+        for (offset, raw) in content[start..end].lines().enumerate() {
+            let line = first_line + offset;
+            if raw.trim().is_empty() {
+                continue;
+            }
+            if let (Some(kind), true) = (list, raw.starts_with("  ")) {
+                let entry = raw.trim().strip_prefix("- ").unwrap_or(raw.trim());
+                let to_error = |reason| DomainError::InvalidStatusFile { line, reason };
+                match kind {
+                    NestedList::History => {
+                        self.history.push(parse_history_entry(entry).map_err(to_error)?)
+                    }
+                    NestedList::IssuedRevisions => self
+                        .issued_revisions
+                        .push(parse_issued_revision(entry).map_err(to_error)?),
+                    NestedList::NeedsRevisit => self
+                        .needs_revisit
+                        .push(parse_revisit_note(entry).map_err(to_error)?),
+                }
+                continue;
+            }
+            list = None;
+            let (key, value) = parse_field(raw).ok_or_else(|| DomainError::InvalidStatusFile {
+                line,
+                reason: format!("expected `- **Key:** value`, found {:?}", raw.trim()),
+            })?;
+            let invalid = |reason: String| DomainError::InvalidStatusFile { line, reason };
+            match key.as_str() {
+                "State" => state = Some(value.parse().map_err(|e: DomainError| invalid(e.to_string()))?),
+                "Last Transition" => {
+                    let (from, to) = value
+                        .split_once("->")
+                        .ok_or_else(|| invalid(format!("expected FROM -> TO, found {:?}", value)))?;
+                    transition = Some((
+                        from.parse().map_err(|e: DomainError| invalid(e.to_string()))?,
+                        to.parse().map_err(|e: DomainError| invalid(e.to_string()))?,
+                    ));
+                }
+                "Reason" => reason = Some(unescape(&value)),
+                "Revision" if !value.is_empty() => self.revision = Some(Revision::from_string(value)),
+                "History" => list = Some(NestedList::History),
+                "Issued Revisions" => list = Some(NestedList::IssuedRevisions),
+                "Needs Revisit" => list = Some(NestedList::NeedsRevisit),
+                "Actor" => {
+                    actor = Some(unescape(&value).parse().map_err(|e: DomainError| invalid(e.to_string()))?)
+                }
+                "Timestamp" => {
+                    let parsed = DateTime::parse_from_rfc3339(&value)
+                        .map_err(|e| invalid(format!("invalid timestamp {:?}: {}", value, e)))?;
+                    at = Some(parsed.with_timezone(&Utc));
+                }
+                _ => {
+                    self.extra_fields.push(ExtraField {
+                        after: previous_known.clone(),
+                        key,
+                        value,
+                    });
+                    continue;
+                }
+            }
+            previous_known = Some(key);
+        }
+
+        self.state = state.ok_or_else(|| DomainError::InvalidStatusFile {
+            line: first_line,
+            reason: "status block has no State field".to_string(),
+        })?;
+        self.last_transition = match (transition, actor, at) {
+            (Some((from, to)), Some(actor), Some(at)) => Some(StateTransition {
+                from,
+                to,
+                actor,
+                at,
+                reason,
+            }),
+            (None, None, None) if reason.is_none() => None,
+            _ => {
+                return Err(DomainError::InvalidStatusFile {
+                    line: first_line,
+                    reason: "Last Transition, Actor and Timestamp must appear together"
+                        .to_string(),
+                })
+            }
+        };
+        Ok(())
+    }
+}
+
+/// A managed-block field the runtime does not understand, kept verbatim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExtraField {
+    /// Known field this one followed; `None` if it came first.
+    after: Option<String>,
+    key: String,
+    value: String,
+}
+
+/// Which nested bullet list the parser is inside.
+#[derive(Clone, Copy)]
+enum NestedList {
+    History,
+    IssuedRevisions,
+    NeedsRevisit,
+}
+
+/// An upstream change that may invalidate a deliverable's documents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevisitNote {
+    pub at: DateTime<Utc>,
+    /// Changed deliverable the impact originates from.
+    pub upstream: DeliverableId,
+    pub reason: String,
+}
+
+/// Byte range of a whole line (including its newline) holding a marker.
+struct LineSpan {
+    start: usize,
+    end: usize,
+}
+
+fn find_marker_line(content: &str, marker: &str, from: usize) -> Option<LineSpan> {
+    let mut start = from;
+    for line in content[from..].split_inclusive('\n') {
+        if line.trim() == marker {
+            return Some(LineSpan {
+                start,
+                end: start + line.len(),
+            });
+        }
+        start += line.len();
+    }
+    None
+}
+
+fn line_number(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+/// Parse `- **Key:** value` (bullet and bold optional).
+fn parse_field(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    let line = line.strip_prefix("- ").unwrap_or(line);
+    let (key, value) = if let Some(rest) = line.strip_prefix("**") {
+        let (key, value) = rest.split_once(":**")?;
+        (key, value)
+    } else {
+        line.split_once(':')?
+    };
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some((key.to_string(), value.trim().to_string()))
+}
+
+/// `TIMESTAMP | FROM -> TO | KIND:id | optional reason`
+fn format_history_entry(entry: &StateTransition) -> String {
+    let mut out = format!(
+        "{} | {} -> {} | {}",
+        entry.at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        entry.from,
+        entry.to,
+        escape_cell(&entry.actor.to_string())
+    );
+    if let Some(reason) = &entry.reason {
+        out.push_str(" | ");
+        out.push_str(&escape(reason));
+    }
+    out
+}
+
+fn parse_history_entry(entry: &str) -> Result<StateTransition, String> {
+    let mut parts = split_cells(entry, 4).into_iter();
+    let (Some(at), Some(states), Some(actor)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(format!(
+            "expected `TIMESTAMP | FROM -> TO | ACTOR [| reason]`, found {:?}",
+            entry
+        ));
+    };
+    let at = DateTime::parse_from_rfc3339(at)
+        .map_err(|e| format!("invalid timestamp {:?}: {}", at, e))?
+        .with_timezone(&Utc);
+    let (from, to) = states
+        .split_once("->")
+        .ok_or_else(|| format!("expected FROM -> TO, found {:?}", states))?;
+    Ok(StateTransition {
+        from: from.parse().map_err(|e: DomainError| e.to_string())?,
+        to: to.parse().map_err(|e: DomainError| e.to_string())?,
+        actor: unescape(actor).parse().map_err(|e: DomainError| e.to_string())?,
+        at,
+        reason: parts.next().filter(|r| !r.is_empty()).map(unescape),
+    })
+}
+
+/// `TIMESTAMP | UPSTREAM | reason`
+fn parse_revisit_note(entry: &str) -> Result<RevisitNote, String> {
+    let parts = split_cells(entry, 3);
+    let [at, upstream, reason] = parts.as_slice() else {
+        return Err(format!("expected `TIMESTAMP | UPSTREAM | reason`, found {:?}", entry));
+    };
+    Ok(RevisitNote {
+        at: DateTime::parse_from_rfc3339(at)
+            .map_err(|e| format!("invalid timestamp {:?}: {}", at, e))?
+            .with_timezone(&Utc),
+        upstream: DeliverableId::from_string(unescape(upstream)),
+        reason: unescape(reason),
+    })
+}
+
+/// `LABEL | TIMESTAMP | KIND:id | File.md=hash; File.md=hash`
+fn format_issued_revision(issued: &IssuedRevision) -> String {
+    let documents: Vec<String> = issued
+        .documents()
+        .iter()
+        .map(|(t, hash)| format!("{}={}", t.filename(), hash))
+        .collect();
+    format!(
+        "{} | {} | {} | {}",
+        escape_cell(issued.revision().as_str()),
+        issued.issued_at().to_rfc3339_opts(SecondsFormat::Secs, true),
+        escape_cell(&issued.issued_by().to_string()),
+        documents.join("; ")
+    )
+}
+
+fn parse_issued_revision(entry: &str) -> Result<IssuedRevision, String> {
+    let parts = split_cells(entry, 4);
+    let [label, at, actor, documents] = parts.as_slice() else {
+        return Err(format!(
+            "expected `REVISION | TIMESTAMP | ACTOR | File.md=hash; …`, found {:?}",
+            entry
+        ));
+    };
+    let at = DateTime::parse_from_rfc3339(at)
+        .map_err(|e| format!("invalid timestamp {:?}: {}", at, e))?
+        .with_timezone(&Utc);
+    let mut snapshot = Vec::new();
+    for item in documents.split(';').map(str::trim).filter(|i| !i.is_empty()) {
+        let (file, hash) = item
+            .split_once('=')
+            .ok_or_else(|| format!("expected File.md=hash, found {:?}", item))?;
+        let document_type = DocumentType::from_filename(file.trim())
+            .ok_or_else(|| format!("unknown document {:?}", file.trim()))?;
+        snapshot.push((document_type, ContentHash::from_string(hash.trim())));
+    }
+    Ok(IssuedRevision::new(
+        Revision::from_string(unescape(label)),
+        at,
+        unescape(actor).parse().map_err(|e: DomainError| e.to_string())?,
+        snapshot,
+    ))
+}
+
+/// Keep free text on one line: `\\`, `\n` and `\r` are written as escapes.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// [`escape`] for a cell of a `|`-separated entry: `|` is written as `\|`.
+fn escape_cell(text: &str) -> String {
+    escape(text).replace('|', "\\|")
+}
+
+/// Split an entry on unescaped `|` into at most `max` trimmed cells; the
+/// last cell keeps any further separators.
+fn split_cells(entry: &str, max: usize) -> Vec<&str> {
+    let mut cells = Vec::new();
+    let mut start = 0;
+    let mut chars = entry.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if cells.len() + 1 == max {
+            break;
+        }
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '|' => {
+                cells.push(entry[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    cells.push(entry[start..].trim());
+    cells
+}
+
+/// Inverse of [`escape`] and [`escape_cell`]; any other backslash is kept
+/// as written.
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some('\\')) => {
+                chars.next();
+                out.push('\\');
+            }
+            ('\\', Some('n')) => {
+                chars.next();
+                out.push('\n');
+            }
+            ('\\', Some('r')) => {
+                chars.next();
+                out.push('\r');
+            }
+            ('\\', Some('|')) => {
+                chars.next();
+                out.push('|');
+            }
+            (c, _) => out.push(c),
+        }
+    }
+    out
+}
+
+fn push_field(out: &mut String, key: &str, value: &str) {
+    out.push_str("- **");
+    out.push_str(key);
+    out.push_str(":** ");
+    out.push_str(value);
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::ActorId;
+    use chrono::TimeZone;
+
+    const SAMPLE: &str = "# Status — DEL-01.01\n\
+        \n\
+        <!-- chirality:status:begin -->\n\
+        - **State:** IN_PROGRESS\n\
+        - **Last Transition:** INITIALIZED -> IN_PROGRESS\n\
+        - **Actor:** HUMAN:alice\n\
+        - **Timestamp:** 2026-01-05T09:30:00Z\n\
+        - **History:**\n\
+        \x20 - 2026-01-02T08:00:00Z | OPEN -> INITIALIZED | AGENT:4_DOCUMENTS\n\
+        \x20 - 2026-01-05T09:30:00Z | INITIALIZED -> IN_PROGRESS | HUMAN:alice\n\
+        - **Reviewer:** bob\n\
+        <!-- chirality:status:end -->\n\
+        \n\
+        ## Notes\n\
+        Waiting on vendor data.   \n\
+        \n\
+        | Item | Owner |\n\
+        |------|-------|\n";
+
+    #[test]
+    fn round_trips_verbatim() {
+        let status = StatusFile::parse(SAMPLE).unwrap();
+        assert_eq!(status.state, DeliverableState::InProgress);
+        let transition = status.last_transition.as_ref().unwrap();
+        assert_eq!(transition.from, DeliverableState::Initialized);
+        assert_eq!(transition.actor, ActorId::human("alice"));
+        assert_eq!(status.history.len(), 2);
+        assert_eq!(status.render(), SAMPLE);
+    }
+
+    #[test]
+    fn transition_rewrites_only_the_block() {
+        let mut status = StatusFile::parse(SAMPLE).unwrap();
+        status.record_transition(StateTransition {
+            from: DeliverableState::InProgress,
+            to: DeliverableState::Checking,
+            actor: ActorId::agent("4_DOCUMENTS"),
+            at: Utc.with_ymd_and_hms(2026, 2, 1, 12, 0, 0).unwrap(),
+            reason: Some("Ready for review | round 1".to_string()),
+        });
+
+        let rendered = status.render();
+        assert!(rendered.contains("- **Last Transition:** IN_PROGRESS -> CHECKING\n"));
+        assert!(rendered.contains("- **Actor:** AGENT:4_DOCUMENTS\n"));
+        assert!(rendered.contains("- **Timestamp:** 2026-02-01T12:00:00Z\n"));
+        assert!(rendered.contains("- **Reason:** Ready for review | round 1\n"));
+        assert!(rendered.contains(
+            "  - 2026-02-01T12:00:00Z | IN_PROGRESS -> CHECKING | AGENT:4_DOCUMENTS | Ready for review | round 1\n"
+        ));
+        assert!(rendered.contains("- **Reviewer:** bob\n"));
+        assert!(rendered.ends_with("## Notes\nWaiting on vendor data.   \n\n| Item | Owner |\n|------|-------|\n"));
+        assert_eq!(StatusFile::parse(&rendered).unwrap(), status);
+    }
+
+    #[test]
+    fn round_trips_revisions() {
+        let mut status = StatusFile::new(DeliverableState::InProgress);
+        status.revision = Some(Revision::from_string("B"));
+        status.issued_revisions.push(IssuedRevision::new(
+            Revision::from_string("A"),
+            Utc.with_ymd_and_hms(2025, 11, 20, 16, 0, 0).unwrap(),
+            ActorId::human("alice"),
+            vec![
+                (DocumentType::Datasheet, ContentHash::from_bytes(b"ds")),
+                (DocumentType::Procedure, ContentHash::from_bytes(b"pr")),
+            ],
+        ));
+
+        let rendered = status.render();
+        assert!(rendered.contains("- **Revision:** B\n"));
+        assert!(rendered.contains("  - A | 2025-11-20T16:00:00Z | HUMAN:alice | Datasheet.md=sha256:"));
+        assert_eq!(StatusFile::parse(&rendered).unwrap(), status);
+    }
+
+    #[test]
+    fn round_trips_revisit_notes() {
+        let mut status = StatusFile::new(DeliverableState::InProgress);
+        let note = RevisitNote {
+            at: Utc.with_ymd_and_hms(2026, 1, 6, 10, 0, 0).unwrap(),
+            upstream: DeliverableId::from_legacy(1, 2),
+            reason: "depends on DEL-01.02, which changed (Datasheet.md)".to_string(),
+        };
+        assert!(status.flag_revisit(note.clone()));
+        assert!(!status.flag_revisit(note));
+
+        let rendered = status.render();
+        assert!(rendered.contains(
+            "- **Needs Revisit:**\n  - 2026-01-06T10:00:00Z | DEL-01.02 | depends on DEL-01.02, which changed (Datasheet.md)\n"
+        ));
+        assert_eq!(StatusFile::parse(&rendered).unwrap(), status);
+    }
+
+    #[test]
+    fn unknown_fields_keep_their_place() {
+        let content = "<!-- chirality:status:begin -->\n\
+            - **Owner:** carol\n\
+            - **State:** IN_PROGRESS\n\
+            - **Reviewer:** bob\n\
+            - **Revision:** A\n\
+            <!-- chirality:status:end -->\n";
+        let mut status = StatusFile::parse(content).unwrap();
+        assert_eq!(status.render(), content);
+
+        status.record_transition(StateTransition {
+            from: DeliverableState::InProgress,
+            to: DeliverableState::Checking,
+            actor: ActorId::human("alice"),
+            at: Utc.with_ymd_and_hms(2026, 2, 1, 12, 0, 0).unwrap(),
+            reason: None,
+        });
+        let rendered = status.render();
+        assert!(rendered.starts_with(
+            "<!-- chirality:status:begin -->\n- **Owner:** carol\n- **State:** CHECKING\n- **Reviewer:** bob\n- **Revision:** A\n- **Last Transition:**"
+        ));
+    }
+
+    #[test]
+    fn escapes_pipes_in_cells() {
+        let mut status = StatusFile::new(DeliverableState::InProgress);
+        status.record_transition(StateTransition {
+            from: DeliverableState::Initialized,
+            to: DeliverableState::InProgress,
+            actor: ActorId::human("alice|bob"),
+            at: Utc.with_ymd_and_hms(2026, 1, 7, 9, 0, 0).unwrap(),
+            reason: Some("split | work".to_string()),
+        });
+        status.flag_revisit(RevisitNote {
+            at: Utc.with_ymd_and_hms(2026, 1, 8, 9, 0, 0).unwrap(),
+            upstream: DeliverableId::from_string("DEL|01"),
+            reason: "changed | twice".to_string(),
+        });
+
+        let rendered = status.render();
+        assert!(rendered.contains("| INITIALIZED -> IN_PROGRESS | HUMAN:alice\\|bob | split | work\n"));
+        assert!(rendered.contains("  - 2026-01-08T09:00:00Z | DEL\\|01 | changed | twice\n"));
+        assert_eq!(StatusFile::parse(&rendered).unwrap(), status);
+    }
+
+    #[test]
+    fn round_trips_multi_line_reasons() {
+        let mut status = StatusFile::new(DeliverableState::InProgress);
+        status.record_transition(StateTransition {
+            from: DeliverableState::InProgress,
+            to: DeliverableState::OnHold,
+            actor: ActorId::human("alice"),
+            at: Utc.with_ymd_and_hms(2026, 1, 7, 9, 0, 0).unwrap(),
+            reason: Some("Waiting on vendor.\n- **State:** ISSUED\nSee C:\\data".to_string()),
+        });
+
+        let rendered = status.render();
+        assert!(rendered.contains("- **Reason:** Waiting on vendor.\\n- **State:** ISSUED\\nSee C:\\\\data\n"));
+        assert_eq!(StatusFile::parse(&rendered).unwrap(), status);
+    }
+
+    #[test]
+    fn file_without_block_is_open_and_keeps_text() {
+        let status = StatusFile::parse("# Status\nSome human notes.\n").unwrap();
+        assert_eq!(status.state, DeliverableState::Open);
+        let rendered = status.render();
+        assert!(rendered.starts_with("# Status\n\n<!-- chirality:status:begin -->\n"));
+        assert!(rendered.ends_with("<!-- chirality:status:end -->\n\nSome human notes.\n"));
+        assert_eq!(StatusFile::parse(&rendered).unwrap().render(), rendered);
+    }
+
+    #[test]
+    fn reports_offending_line() {
+        let content = "# Status\n<!-- chirality:status:begin -->\n- **State:** FINISHED\n<!-- chirality:status:end -->\n";
+        match StatusFile::parse(content) {
+            Err(DomainError::InvalidStatusFile { line, .. }) => assert_eq!(line, 3),
+            other => panic!("unexpected: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_unclosed_block() {
+        let content = "<!-- chirality:status:begin -->\n- **State:** OPEN\n";
+        assert!(matches!(
+            StatusFile::parse(content),
+            Err(DomainError::InvalidStatusFile { line: 1, .. })
+        ));
+    }
+}
@@ -3,11 +3,13 @@
 //! From chirality-app: Agents have explicit write zones. This module
 //! validates that all filesystem writes stay within declared scopes.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 use crate::entities::DeliverableId;
 use crate::error::DomainError;
+use crate::path_rules;
 
 /// Write scope for an agent session.
 ///
@@ -26,6 +28,43 @@ pub enum WriteScope {
     ToolRootOnly { root_path: PathBuf },
     /// Only project-level metadata (e.g., _COORDINATION.md)
     RepoMetadataOnly { allowed_files: Vec<PathBuf> },
+    /// Paths under `root` matching a glob (e.g., `PKG-*/DEL-*/_SEMANTIC.md`).
+    ///
+    /// `*` and `?` stay within one path segment; `**` spans any number of them.
+    Glob { root: PathBuf, pattern: String },
+    /// Any of `allow`, except paths matched by a `deny` rule (deny wins).
+    Composite {
+        allow: Vec<WriteScope>,
+        #[serde(default)]
+        deny: Vec<WriteScope>,
+    },
+}
+
+impl WriteScope {
+    /// Short description used in violations (e.g. `ToolRootOnly(/p/_Aggregation)`).
+    pub fn describe(&self) -> String {
+        match self {
+            WriteScope::None => "None".to_string(),
+            WriteScope::DeliverableLocal {
+                deliverable_path, ..
+            } => format!("DeliverableLocal({})", deliverable_path.display()),
+            WriteScope::ToolRootOnly { root_path } => {
+                format!("ToolRootOnly({})", root_path.display())
+            }
+            WriteScope::RepoMetadataOnly { allowed_files } => {
+                format!("RepoMetadataOnly({:?})", allowed_files)
+            }
+            WriteScope::Glob { root, pattern } => {
+                format!("Glob({})", root.join(pattern).display())
+            }
+            WriteScope::Composite { allow, deny } => {
+                let list = |rules: &[WriteScope]| {
+                    rules.iter().map(|r| r.describe()).collect::<Vec<_>>().join(", ")
+                };
+                format!("Composite(allow: [{}], deny: [{}])", list(allow), list(deny))
+            }
+        }
+    }
 }
 
 /// Result of write validation.
@@ -41,6 +80,34 @@ pub struct WriteViolation {
     pub target_path: PathBuf,
     pub scope: String,
     pub reason: String,
+    /// Deny rule that matched the path, for composite scopes.
+    pub rule: Option<String>,
+}
+
+/// Kind of filesystem mutation checked against a write scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WriteOperation {
+    Write,
+    Delete,
+    CreateDir,
+    Scaffold,
+}
+
+/// One checked write, kept for the session audit trail.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteAttempt {
+    pub at: DateTime<Utc>,
+    pub operation: WriteOperation,
+    pub path: PathBuf,
+    /// Violation reason when the write was denied.
+    pub denied: Option<String>,
+}
+
+impl WriteAttempt {
+    pub fn is_allowed(&self) -> bool {
+        self.denied.is_none()
+    }
 }
 
 /// Validates write operations against declared scopes.
@@ -54,12 +121,13 @@ impl WriteGuard {
                 target_path: target_path.to_path_buf(),
                 scope: "None".to_string(),
                 reason: "Agent has no write permission".to_string(),
+                rule: None,
             }),
 
             WriteScope::DeliverableLocal {
                 deliverable_path, ..
             } => {
-                if Self::is_within(target_path, deliverable_path) {
+                if path_rules::is_within(target_path, deliverable_path) {
                     WriteValidation::Allowed
                 } else {
                     WriteValidation::Denied(WriteViolation {
@@ -69,24 +137,29 @@ impl WriteGuard {
                             "Path is outside deliverable folder: {}",
                             deliverable_path.display()
                         ),
+                        rule: None,
                     })
                 }
             }
 
             WriteScope::ToolRootOnly { root_path } => {
-                if Self::is_within(target_path, root_path) {
+                if path_rules::is_within(target_path, root_path) {
                     WriteValidation::Allowed
                 } else {
                     WriteValidation::Denied(WriteViolation {
                         target_path: target_path.to_path_buf(),
                         scope: format!("ToolRootOnly({})", root_path.display()),
                         reason: format!("Path is outside tool root: {}", root_path.display()),
+                        rule: None,
                     })
                 }
             }
 
             WriteScope::RepoMetadataOnly { allowed_files } => {
-                if allowed_files.iter().any(|f| f == target_path) {
+                if allowed_files
+                    .iter()
+                    .any(|f| path_rules::is_same_file(target_path, f))
+                {
                     WriteValidation::Allowed
                 } else {
                     WriteValidation::Denied(WriteViolation {
@@ -96,22 +169,50 @@ impl WriteGuard {
                             "Path is not in allowed metadata files: {:?}",
                             allowed_files
                         ),
+                        rule: None,
                     })
                 }
             }
-        }
-    }
 
-    /// Check if child is within parent directory.
-    fn is_within(child: &Path, parent: &Path) -> bool {
-        // Normalize paths for comparison
-        let child_canonical = child.canonicalize().ok();
-        let parent_canonical = parent.canonicalize().ok();
+            WriteScope::Glob { root, pattern } => {
+                if path_rules::glob_allows(root, pattern, target_path) {
+                    WriteValidation::Allowed
+                } else {
+                    WriteValidation::Denied(WriteViolation {
+                        target_path: target_path.to_path_buf(),
+                        scope: scope.describe(),
+                        reason: format!(
+                            "Path does not match {} under {}",
+                            pattern,
+                            root.display()
+                        ),
+                        rule: None,
+                    })
+                }
+            }
 
-        match (child_canonical, parent_canonical) {
-            (Some(c), Some(p)) => c.starts_with(&p),
-            // If we can't canonicalize, fall back to prefix check
-            _ => child.starts_with(parent),
+            WriteScope::Composite { allow, deny } => {
+                let allows = |rule: &WriteScope| {
+                    matches!(Self::validate_write(rule, target_path), WriteValidation::Allowed)
+                };
+                if let Some(rule) = deny.iter().find(|r| allows(r)) {
+                    WriteValidation::Denied(WriteViolation {
+                        target_path: target_path.to_path_buf(),
+                        scope: scope.describe(),
+                        reason: format!("Path matches deny rule {}", rule.describe()),
+                        rule: Some(rule.describe()),
+                    })
+                } else if allow.iter().any(allows) {
+                    WriteValidation::Allowed
+                } else {
+                    WriteValidation::Denied(WriteViolation {
+                        target_path: target_path.to_path_buf(),
+                        scope: scope.describe(),
+                        reason: "Path matches no allow rule".to_string(),
+                        rule: None,
+                    })
+                }
+            }
         }
     }
 
@@ -176,4 +277,186 @@ mod tests {
         );
         assert!(matches!(result, WriteValidation::Allowed));
     }
+
+    #[test]
+    fn glob_matches_within_and_across_segments() {
+        let scope = WriteScope::Glob {
+            root: PathBuf::from("/project"),
+            pattern: "PKG-*/DEL-*/_SEMANTIC.md".to_string(),
+        };
+        let allowed = |p: &str| {
+            matches!(
+                WriteGuard::validate_write(&scope, Path::new(p)),
+                WriteValidation::Allowed
+            )
+        };
+        assert!(allowed("/project/PKG-01_Civil/DEL-01.01/_SEMANTIC.md"));
+        assert!(!allowed("/project/PKG-01/DEL-01.01/Datasheet.md"));
+        assert!(!allowed("/project/PKG-01/nested/DEL-01.01/_SEMANTIC.md"));
+        assert!(!allowed("/project/PKG-01/../../etc/PKG-01/DEL-01.01/_SEMANTIC.md"));
+
+        let deep = WriteScope::Glob {
+            root: PathBuf::from("/project"),
+            pattern: "execution/**/*.json".to_string(),
+        };
+        assert!(matches!(
+            WriteGuard::validate_write(&deep, Path::new("/project/execution/a/b/out.json")),
+            WriteValidation::Allowed
+        ));
+    }
+
+    #[test]
+    fn composite_unions_allow_rules_and_deny_wins() {
+        let scope = WriteScope::Composite {
+            allow: vec![
+                WriteScope::ToolRootOnly {
+                    root_path: PathBuf::from("/project/execution/_Aggregation"),
+                },
+                WriteScope::RepoMetadataOnly {
+                    allowed_files: vec![PathBuf::from("/project/_COORDINATION.md")],
+                },
+            ],
+            deny: vec![WriteScope::Glob {
+                root: PathBuf::from("/project"),
+                pattern: "**/_STATUS.md".to_string(),
+            }],
+        };
+
+        for allowed in [
+            "/project/execution/_Aggregation/snapshot.json",
+            "/project/_COORDINATION.md",
+        ] {
+            assert!(matches!(
+                WriteGuard::validate_write(&scope, Path::new(allowed)),
+                WriteValidation::Allowed
+            ));
+        }
+        match WriteGuard::validate_write(
+            &scope,
+            Path::new("/project/execution/_Aggregation/_STATUS.md"),
+        ) {
+            WriteValidation::Denied(violation) => {
+                assert_eq!(violation.rule.as_deref(), Some("Glob(/project/**/_STATUS.md)"));
+            }
+            WriteValidation::Allowed => panic!("deny rule should take precedence"),
+        }
+        assert!(matches!(
+            WriteGuard::validate_write(&scope, Path::new("/project/PKG-01/DEL-01.01/Datasheet.md")),
+            WriteValidation::Denied(WriteViolation { rule: None, .. })
+        ));
+    }
+
+    /// Escape attempts that must never pass a scope check.
+    mod escapes {
+        use super::*;
+        use std::fs;
+
+        fn deliverable_scope(path: impl Into<PathBuf>) -> WriteScope {
+            WriteScope::DeliverableLocal {
+                deliverable_id: DeliverableId::from_string("del:test"),
+                deliverable_path: path.into(),
+            }
+        }
+
+        fn allowed(scope: &WriteScope, path: impl AsRef<Path>) -> bool {
+            matches!(
+                WriteGuard::validate_write(scope, path.as_ref()),
+                WriteValidation::Allowed
+            )
+        }
+
+        /// Scratch project tree under the system temp dir, removed on drop.
+        struct TempTree(PathBuf);
+
+        impl TempTree {
+            fn new() -> Self {
+                let root = std::env::temp_dir().join(format!("chirality-guard-{}", ulid::Ulid::new()));
+                fs::create_dir_all(root.join("project/PKG-01/DEL-01.01")).unwrap();
+                fs::create_dir_all(root.join("outside")).unwrap();
+                fs::write(root.join("outside/secret.md"), "secret").unwrap();
+                Self(root)
+            }
+
+            fn path(&self, relative: &str) -> PathBuf {
+                self.0.join(relative)
+            }
+        }
+
+        impl Drop for TempTree {
+            fn drop(&mut self) {
+                let _ = fs::remove_dir_all(&self.0);
+            }
+        }
+
+        #[test]
+        fn parent_dir_traversal_out_of_deliverable() {
+            let scope = deliverable_scope("/project/PKG-01/DEL-01.01");
+            assert!(!allowed(&scope, "/project/PKG-01/DEL-01.01/../../PKG-02/x.md"));
+            assert!(!allowed(&scope, "/project/PKG-01/DEL-01.01/../DEL-01.010/x.md"));
+            assert!(!allowed(&scope, "/project/PKG-01/DEL-01.01/../../../../../etc/passwd"));
+            assert!(allowed(&scope, "/project/PKG-01/DEL-01.01/drafts/../Datasheet.md"));
+            assert!(allowed(&scope, "/project/PKG-01/./DEL-01.01/Datasheet.md"));
+        }
+
+        #[test]
+        fn parent_dir_traversal_out_of_tool_root() {
+            let scope = WriteScope::ToolRootOnly {
+                root_path: PathBuf::from("/project/execution/_Aggregation"),
+            };
+            assert!(!allowed(&scope, "/project/execution/_Aggregation/../_Other/x.json"));
+            assert!(!allowed(&scope, "/project/execution/_AggregationX/x.json"));
+        }
+
+        #[test]
+        fn repo_metadata_compares_normalised_paths() {
+            let scope = WriteScope::RepoMetadataOnly {
+                allowed_files: vec![PathBuf::from("/project/_COORDINATION.md")],
+            };
+            assert!(allowed(&scope, "/project/PKG-01/../_COORDINATION.md"));
+            assert!(!allowed(&scope, "/project/PKG-01/../../_COORDINATION.md"));
+            assert!(!allowed(&scope, "/project/PKG-01/_COORDINATION.md"));
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn symlinks_pointing_outside_are_rejected() {
+            use std::os::unix::fs::symlink;
+
+            let tree = TempTree::new();
+            let deliverable = tree.path("project/PKG-01/DEL-01.01");
+            symlink(tree.path("outside"), deliverable.join("linked_dir")).unwrap();
+            symlink(tree.path("outside/secret.md"), deliverable.join("linked.md")).unwrap();
+            symlink(tree.path("outside/missing.md"), deliverable.join("dangling.md")).unwrap();
+            let scope = deliverable_scope(&deliverable);
+
+            assert!(!allowed(&scope, deliverable.join("linked_dir/new.md")));
+            assert!(!allowed(&scope, deliverable.join("linked.md")));
+            assert!(!allowed(&scope, deliverable.join("dangling.md")));
+            assert!(allowed(&scope, deliverable.join("new.md")));
+            // `..` after a missing component must not skip the link check.
+            assert!(!allowed(&scope, deliverable.join("missing/../linked_dir/new.md")));
+            assert!(!allowed(&scope, deliverable.join("a/b/../../linked.md")));
+            assert!(allowed(&scope, deliverable.join("missing/../drafts/new.md")));
+
+            let metadata = WriteScope::RepoMetadataOnly {
+                allowed_files: vec![deliverable.join("linked.md")],
+            };
+            assert!(allowed(&metadata, deliverable.join("linked.md")));
+            assert!(!allowed(&metadata, tree.path("project/_COORDINATION.md")));
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn symlinked_scope_root_still_contains_its_files() {
+            use std::os::unix::fs::symlink;
+
+            let tree = TempTree::new();
+            symlink(tree.path("project/PKG-01/DEL-01.01"), tree.path("current")).unwrap();
+            let scope = deliverable_scope(tree.path("current"));
+
+            assert!(allowed(&scope, tree.path("current/Datasheet.md")));
+            assert!(allowed(&scope, tree.path("project/PKG-01/DEL-01.01/Datasheet.md")));
+            assert!(!allowed(&scope, tree.path("current/../outside/secret.md")));
+        }
+    }
 }
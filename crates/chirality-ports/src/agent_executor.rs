@@ -3,7 +3,7 @@
 use async_trait::async_trait;
 use std::path::PathBuf;
 
-use chirality_domain::{SessionBrief, SessionOutput, WriteScope};
+use chirality_domain::{ReadScope, SessionBrief, SessionOutput, WriteScope};
 
 use crate::error::PortError;
 
@@ -41,6 +41,8 @@ pub struct ExecutionContext {
     pub agent_instructions: String,
     /// Write scope for the session.
     pub write_scope: WriteScope,
+    /// Read scope for the session; `context_files` and file-read tools obey it.
+    pub read_scope: ReadScope,
     /// Path to the deliverable (if scoped to one).
     pub deliverable_path: Option<PathBuf>,
     /// Additional context files.
@@ -3,6 +3,8 @@
 use std::path::PathBuf;
 use thiserror::Error;
 
+use chirality_domain::ContentHash;
+
 /// Errors from port operations.
 #[derive(Debug, Error)]
 pub enum PortError {
@@ -10,12 +12,25 @@ pub enum PortError {
     #[error("File not found: {path:?}")]
     FileNotFound { path: PathBuf },
 
-    #[error("Permission denied: {path:?}")]
-    PermissionDenied { path: PathBuf },
+    #[error("Permission denied: {path:?} ({reason})")]
+    PermissionDenied { path: PathBuf, reason: String },
 
     #[error("IO error: {message}")]
     Io { message: String },
 
+    /// A conditional write found different content than expected; `None`
+    /// means the file does not exist.
+    #[error(
+        "Write conflict on {path:?}: expected {}, found {}",
+        describe_hash(.expected),
+        describe_hash(.actual)
+    )]
+    WriteConflict {
+        path: PathBuf,
+        expected: Option<ContentHash>,
+        actual: Option<ContentHash>,
+    },
+
     // Git errors
     #[error("Git error: {message}")]
     Git { message: String },
@@ -62,3 +77,10 @@ impl From<std::io::Error> for PortError {
         }
     }
 }
+
+fn describe_hash(hash: &Option<ContentHash>) -> String {
+    match hash {
+        Some(hash) => hash.to_string(),
+        None => "no file".to_string(),
+    }
+}
@@ -41,6 +41,13 @@ pub trait GitPort: Send + Sync {
     /// Get commit history for a path.
     async fn log(&self, path: Option<&Path>, limit: usize) -> Result<Vec<CommitInfo>, PortError>;
 
+    /// Paths changed between two commits (`from..to`), relative to the repository root.
+    async fn changed_paths(
+        &self,
+        from: &CommitHash,
+        to: &CommitHash,
+    ) -> Result<Vec<PathBuf>, PortError>;
+
     /// Create a tag.
     async fn tag(&self, name: &str, message: Option<&str>) -> Result<(), PortError>;
 }
@@ -1,7 +1,10 @@
 //! Workspace port for filesystem operations.
 
 use async_trait::async_trait;
+use futures_core::Stream;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use ulid::Ulid;
 
 use chirality_domain::{ContentHash, Deliverable};
 
@@ -16,12 +19,29 @@ pub trait WorkspacePort: Send + Sync {
     /// Write file content, returning content hash.
     async fn write(&self, path: &Path, content: &[u8]) -> Result<ContentHash, PortError>;
 
+    /// Write file content only if the file still hashes to `expected`
+    /// (`None`: only if it does not exist yet).
+    ///
+    /// Fails with `PortError::WriteConflict`, leaving the file untouched,
+    /// when the content changed underneath the caller. The check only
+    /// protects against other `write_if_unchanged` callers and edits made
+    /// before it runs; a plain [`WorkspacePort::write`] is never blocked.
+    async fn write_if_unchanged(
+        &self,
+        path: &Path,
+        content: &[u8],
+        expected: Option<&ContentHash>,
+    ) -> Result<ContentHash, PortError>;
+
     /// List directory contents.
     async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>, PortError>;
 
     /// Check if path exists.
     async fn exists(&self, path: &Path) -> Result<bool, PortError>;
 
+    /// Check if path exists and is a directory.
+    async fn is_dir(&self, path: &Path) -> Result<bool, PortError>;
+
     /// Compute content hash of a file.
     async fn hash(&self, path: &Path) -> Result<ContentHash, PortError>;
 
@@ -33,10 +53,108 @@ pub trait WorkspacePort: Send + Sync {
 
     /// Scaffold deliverable folder structure.
     async fn scaffold_deliverable(&self, deliverable: &Deliverable) -> Result<(), PortError>;
+
+    /// Watch a directory subtree for changes.
+    ///
+    /// Events are debounced and coalesced per path; `.git/`, runtime
+    /// bookkeeping and editor temporary files are never reported. Dropping the stream stops watching.
+    async fn watch(&self, path: &Path) -> Result<FsChangeStream, PortError>;
+
+    /// Apply every staged change in the transaction, or none of them.
+    ///
+    /// If applying fails part way, changes already made are rolled back
+    /// before the error is returned. Returns the hash of each staged write,
+    /// in staging order.
+    async fn commit_transaction(
+        &self,
+        transaction: WorkspaceTransaction,
+    ) -> Result<Vec<(PathBuf, ContentHash)>, PortError>;
+
+    /// Roll back transactions interrupted mid-commit (e.g. by a crash).
+    ///
+    /// Whoever opens the workspace at startup must call this once, before
+    /// anything else touches it (`FilesystemAdapter::open` does). Never call
+    /// it while a commit may be in flight. Returns the ids of the
+    /// transactions that were rolled back.
+    async fn recover_transactions(&self) -> Result<Vec<String>, PortError>;
 }
 
-/// Filesystem change event for watchers.
+/// A set of writes and deletes applied all-or-nothing by
+/// [`WorkspacePort::commit_transaction`].
+///
+/// Nothing touches the workspace until the transaction is committed; a
+/// transaction that is rolled back (or simply dropped) leaves no trace.
 #[derive(Debug, Clone)]
+pub struct WorkspaceTransaction {
+    id: String,
+    changes: Vec<StagedChange>,
+}
+
+/// One change staged in a [`WorkspaceTransaction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StagedChange {
+    Write { path: PathBuf, content: Vec<u8> },
+    Delete { path: PathBuf },
+}
+
+impl StagedChange {
+    pub fn path(&self) -> &Path {
+        match self {
+            StagedChange::Write { path, .. } | StagedChange::Delete { path } => path,
+        }
+    }
+}
+
+impl WorkspaceTransaction {
+    pub fn begin() -> Self {
+        Self {
+            id: Ulid::new().to_string(),
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Stage a write. A later change to the same path replaces this one;
+    /// paths are compared as written, and adapters dedup again after
+    /// resolving them.
+    pub fn write(&mut self, path: impl Into<PathBuf>, content: impl Into<Vec<u8>>) -> &mut Self {
+        self.stage(StagedChange::Write {
+            path: path.into(),
+            content: content.into(),
+        })
+    }
+
+    /// Stage a delete. A later change to the same path replaces this one.
+    pub fn delete(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.stage(StagedChange::Delete { path: path.into() })
+    }
+
+    /// Discard the transaction without applying anything.
+    pub fn rollback(self) {}
+
+    pub fn changes(&self) -> &[StagedChange] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn stage(&mut self, change: StagedChange) -> &mut Self {
+        self.changes.retain(|c| c.path() != change.path());
+        self.changes.push(change);
+        self
+    }
+}
+
+/// Stream of filesystem changes returned by [`WorkspacePort::watch`].
+pub type FsChangeStream = Pin<Box<dyn Stream<Item = FsChangeEvent> + Send>>;
+
+/// Filesystem change event for watchers.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FsChangeEvent {
     pub path: PathBuf,
     pub change_type: FsChangeType,